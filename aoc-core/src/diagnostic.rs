@@ -0,0 +1,120 @@
+use std::fmt;
+
+/// A single input line with carets marking the offending span, for error messages.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Snippet {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column (in characters) of the first marked character.
+    pub column: usize,
+    /// Number of characters marked; always at least one.
+    pub width: usize,
+    /// The full text of the line.
+    pub text: String,
+}
+
+impl Snippet {
+    /// Marks `len` bytes starting at byte offset `start` of `text`.
+    ///
+    /// An empty span (e.g. a missing token at the end of a line) is shown as a single caret.
+    pub fn new(line: usize, text: &str, start: usize, len: usize) -> Self {
+        let column = text[..start].chars().count() + 1;
+        let width = text[start..start + len].chars().count().max(1);
+        Snippet {
+            line,
+            column,
+            width,
+            text: text.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Snippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+        // Keep tabs in the padding so the carets line up with the text above them.
+        let padding: String = self
+            .text
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(f, "{:gutter$} | {padding}{}", "", "^".repeat(self.width))
+    }
+}
+
+/// An error that can be tallied by kind in a [`Diagnostics`] summary.
+pub trait Diagnostic: fmt::Display {
+    /// Singular noun phrase naming the kind of problem, e.g. `"malformed line"`.
+    fn kind(&self) -> &'static str;
+}
+
+/// Every problem found while validating a whole input, in input order.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnostics<E> {
+    pub errors: Vec<E>,
+}
+
+impl<E: Diagnostic> Diagnostics<E> {
+    /// True if the input passed validation.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// How many problems of each kind were found, in order of first appearance.
+    pub fn summary(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<(&'static str, usize)> = Vec::new();
+        for kind in self.errors.iter().map(Diagnostic::kind) {
+            match counts.iter_mut().find(|(k, _)| *k == kind) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((kind, 1)),
+            }
+        }
+        counts
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+impl<E: Diagnostic> fmt::Display for Diagnostics<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no problems found");
+        }
+        for error in &self.errors {
+            writeln!(f, "{error}\n")?;
+        }
+        let breakdown: Vec<String> = self
+            .summary()
+            .into_iter()
+            .map(|(kind, count)| plural(count, kind))
+            .collect();
+        write!(
+            f,
+            "{} found: {}",
+            plural(self.errors.len(), "problem"),
+            breakdown.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carets_point_at_span() {
+        let snippet = Snippet::new(12, "A\tQ", 2, 1);
+        assert_eq!(snippet.column, 3);
+        assert_eq!(snippet.to_string(), "   |\n12 | A\tQ\n   |  \t^");
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+/// Environment variable consulted when no input argument is given.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+/// Where a puzzle input is read from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Interprets a command-line argument: `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// Picks the explicit argument if present, then `AOC_INPUT`, then `default`.
+    pub fn resolve(arg: Option<&str>, default: impl Into<PathBuf>) -> Self {
+        if let Some(arg) = arg {
+            return Self::from_arg(arg);
+        }
+        match std::env::var(INPUT_ENV) {
+            Ok(value) if !value.is_empty() => Self::from_arg(&value),
+            _ => InputSource::File(default.into()),
+        }
+    }
+
+    /// Reads the whole input into memory.
+    pub fn read(&self) -> Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .context("Failed to read input from stdin")?;
+                Ok(contents)
+            }
+            InputSource::File(path) => fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Opens the input for reading line by line, without loading it all into memory.
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::File(path) => {
+                let file = fs::File::open(path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => f.write_str("<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dash_means_stdin() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::resolve(Some("puzzle.txt"), "input.txt"),
+            InputSource::File(PathBuf::from("puzzle.txt"))
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+pub mod diagnostic;
+pub mod format;
+pub mod input;
+pub mod mode;
+pub mod record;
+pub mod timing;
+
+pub use diagnostic::{Diagnostic, Diagnostics, Snippet};
+pub use format::Format;
+pub use input::InputSource;
+pub use mode::{Irregularity, ParseMode};
+pub use record::{Record, input_hash};
+pub use timing::{Phase, PhaseSummary};
+
+/// One of the two puzzle parts each day is split into.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// The 1-based part number.
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A day's puzzle: the input is parsed once, then both parts are answered from it.
+pub trait Solution {
+    /// Day of the advent calendar this solves.
+    const DAY: u8;
+
+    /// Path of the puzzle input used when none is given on the command line.
+    const DEFAULT_INPUT: &'static str;
+
+    /// Path of the puzzle's worked example, committed with the day so there is always an
+    /// input to check answers against.
+    const EXAMPLE_INPUT: &'static str;
+
+    /// Parsed representation shared by both parts.
+    type Input;
+    /// Answer type of part one.
+    type PartOne: Display;
+    /// Answer type of part two.
+    type PartTwo: Display;
+
+    /// Parses the raw puzzle input, as forgivingly or as strictly as `mode` says.
+    fn parse_with(input: &str, mode: ParseMode) -> Result<Self::Input>;
+
+    /// Parses the raw puzzle input in [`ParseMode::Lenient`].
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, ParseMode::Lenient)
+    }
+
+    /// Solves part one from the parsed input.
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne>;
+
+    /// Solves part two from the parsed input.
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo>;
+}
+
+/// One part's answer, rendered as text, and how long it took once the input was parsed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+/// A day's input parsed once, with every requested part answered from it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Solved {
+    /// How long parsing took; it is shared by every answer.
+    pub parse: Duration,
+    /// One answer per requested part, in the order they were asked for.
+    pub answers: Vec<Answer>,
+}
+
+/// Parses `input` with `S` in `mode` once, then renders the answer to each of `parts`.
+pub fn solve<S: Solution>(input: &str, parts: &[Part], mode: ParseMode) -> Result<Solved> {
+    let start = Instant::now();
+    let parsed = S::parse_with(input, mode)?;
+    let parse = start.elapsed();
+
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let value = match part {
+                Part::One => S::part_one(&parsed).map(|answer| answer.to_string()),
+                Part::Two => S::part_two(&parsed).map(|answer| answer.to_string()),
+            }
+            .with_context(|| format!("Part {} failed", part.number()))?;
+            Ok(Answer {
+                part,
+                value,
+                elapsed: start.elapsed(),
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(Solved { parse, answers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PARSES: AtomicUsize = AtomicUsize::new(0);
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        const DAY: u8 = 0;
+        const DEFAULT_INPUT: &'static str = "";
+        const EXAMPLE_INPUT: &'static str = "";
+
+        type Input = Vec<usize>;
+        type PartOne = usize;
+        type PartTwo = usize;
+
+        fn parse_with(input: &str, _: ParseMode) -> Result<Self::Input> {
+            PARSES.fetch_add(1, Ordering::SeqCst);
+            Ok(input.lines().map(str::len).collect())
+        }
+
+        fn part_one(input: &Self::Input) -> Result<usize> {
+            Ok(input.len())
+        }
+
+        fn part_two(input: &Self::Input) -> Result<usize> {
+            Ok(input.iter().sum())
+        }
+    }
+
+    #[test]
+    fn solve_parses_once_for_every_part() -> Result<()> {
+        let solved = solve::<Lengths>("ab\ncde\n", &Part::ALL, ParseMode::Lenient)?;
+        assert_eq!(PARSES.load(Ordering::SeqCst), 1);
+        let values: Vec<_> = solved
+            .answers
+            .iter()
+            .map(|a| (a.part, a.value.as_str()))
+            .collect();
+        assert_eq!(values, vec![(Part::One, "2"), (Part::Two, "5")]);
+        Ok(())
+    }
+}
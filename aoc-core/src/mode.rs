@@ -0,0 +1,113 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// How forgiving a parser is about the layout of its input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ParseMode {
+    /// Skip blank and whitespace-only lines, trim tokens and ignore anything after the last one.
+    #[default]
+    Lenient,
+    /// Reject any layout the puzzle's own inputs never have; see [`Irregularity`].
+    Strict,
+}
+
+impl FromStr for ParseMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lenient" => Ok(ParseMode::Lenient),
+            "strict" => Ok(ParseMode::Strict),
+            other => Err(format!(
+                "unknown parse mode {other:?}, expected lenient or strict"
+            )),
+        }
+    }
+}
+
+/// Something [`ParseMode::Strict`] rejects that [`ParseMode::Lenient`] lets through.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Irregularity {
+    /// A tab anywhere on a line.
+    Tab,
+    /// Whitespace at either end of a line, or more than one space between tokens.
+    StrayWhitespace,
+    /// A number written with a `+` sign.
+    LeadingPlus,
+    /// A token after the last one the line should have.
+    TrailingToken,
+    /// A blank line that doesn't separate two blocks.
+    ExtraBlankLine,
+}
+
+impl fmt::Display for Irregularity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Irregularity::Tab => "tab",
+            Irregularity::StrayWhitespace => "stray whitespace",
+            Irregularity::LeadingPlus => "leading `+` sign",
+            Irregularity::TrailingToken => "trailing token",
+            Irregularity::ExtraBlankLine => "extra blank line",
+        })
+    }
+}
+
+impl ParseMode {
+    /// In strict mode, the first tab or stray whitespace on a non-empty `line`, with the byte
+    /// offset and length of the span to point at.
+    ///
+    /// Tokens must be separated by exactly one space, with nothing before the first or after
+    /// the last.
+    pub fn check_spacing(self, line: &str) -> Option<(Irregularity, usize, usize)> {
+        if self == ParseMode::Lenient {
+            return None;
+        }
+        if let Some(start) = line.find('\t') {
+            return Some((Irregularity::Tab, start, 1));
+        }
+        let leading = line.len() - line.trim_start().len();
+        if leading > 0 {
+            return Some((Irregularity::StrayWhitespace, 0, leading));
+        }
+        let trimmed = line.trim_end();
+        if trimmed.len() < line.len() {
+            return Some((
+                Irregularity::StrayWhitespace,
+                trimmed.len(),
+                line.len() - trimmed.len(),
+            ));
+        }
+        let gap = line.find("  ")?;
+        let len = line[gap..].len() - line[gap..].trim_start().len();
+        Some((Irregularity::StrayWhitespace, gap, len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_spacing_allows_single_spaces_only() {
+        let strict = ParseMode::Strict;
+        assert_eq!(strict.check_spacing("A Y"), None);
+        assert_eq!(
+            strict.check_spacing("A\tY"),
+            Some((Irregularity::Tab, 1, 1))
+        );
+        assert_eq!(
+            strict.check_spacing("  12"),
+            Some((Irregularity::StrayWhitespace, 0, 2))
+        );
+        assert_eq!(
+            strict.check_spacing("12 "),
+            Some((Irregularity::StrayWhitespace, 2, 1))
+        );
+        assert_eq!(
+            strict.check_spacing("A   Y"),
+            Some((Irregularity::StrayWhitespace, 1, 3))
+        );
+        assert_eq!(ParseMode::Lenient.check_spacing(" A\tY "), None);
+        assert_eq!("strict".parse(), Ok(ParseMode::Strict));
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::Part;
+
+/// One answer as printed by `--format json`, a single JSON object per line.
+///
+/// Objects carry `"kind": "answer"`, so they can share a stream with
+/// [`PhaseSummary`](crate::PhaseSummary)'s `"kind": "timing"` ones.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(tag = "kind", rename = "answer")]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    /// The answer as text mode prints it; a string so wide integers survive any JSON parser.
+    pub answer: String,
+    /// Nanoseconds spent parsing the input and answering this part.
+    pub elapsed_ns: u64,
+    /// [`input_hash`] of the raw input as 16 hex digits, to tell which input was answered.
+    pub input_hash: String,
+}
+
+impl Record {
+    pub fn new(day: u8, part: Part, answer: impl Display, elapsed: Duration, hash: u64) -> Self {
+        Record {
+            day,
+            part: part.number(),
+            answer: answer.to_string(),
+            elapsed_ns: u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+            input_hash: format!("{hash:016x}"),
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash of `input`: cheap, stable across runs and platforms, not cryptographic.
+pub fn input_hash(input: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    input.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_carry_the_fnv1a_hash_of_the_input() {
+        assert_eq!(input_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(input_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+
+        let record = Record::new(2, Part::Two, 12, Duration::from_micros(3), input_hash(b"a"));
+        assert_eq!(
+            record,
+            Record {
+                day: 2,
+                part: 2,
+                answer: "12".to_string(),
+                elapsed_ns: 3000,
+                input_hash: "af63dc4c8601ec8c".to_string(),
+            }
+        );
+        assert!(
+            serde_json::to_string(&record)
+                .unwrap()
+                .starts_with(r#"{"kind":"answer","day":2,"#)
+        );
+    }
+}
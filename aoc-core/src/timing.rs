@@ -0,0 +1,133 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::{Part, Solved};
+
+/// A step of answering a day, in the order they run.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Read,
+    Parse,
+    PartOne,
+    PartTwo,
+}
+
+impl Phase {
+    /// The phase that answers `part`.
+    pub fn part(part: Part) -> Self {
+        match part {
+            Part::One => Phase::PartOne,
+            Part::Two => Phase::PartTwo,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Phase::Read => "read",
+            Phase::Parse => "parse",
+            Phase::PartOne => "part 1",
+            Phase::PartTwo => "part 2",
+        })
+    }
+}
+
+impl Solved {
+    /// How long parsing and each answer took, in the order they ran.
+    pub fn phases(&self) -> Vec<(Phase, Duration)> {
+        let answers = self
+            .answers
+            .iter()
+            .map(|answer| (Phase::part(answer.part), answer.elapsed));
+        [(Phase::Parse, self.parse)]
+            .into_iter()
+            .chain(answers)
+            .collect()
+    }
+}
+
+/// The fastest and the median of several timings of one day's phase.
+///
+/// As JSON it carries `"kind": "timing"` to tell it apart from a [`Record`](crate::Record).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(tag = "kind", rename = "timing")]
+pub struct PhaseSummary {
+    pub day: u8,
+    pub phase: Phase,
+    pub runs: usize,
+    #[serde(rename = "min_ns", serialize_with = "nanos")]
+    pub min: Duration,
+    /// The lower middle sample when `runs` is even.
+    #[serde(rename = "median_ns", serialize_with = "nanos")]
+    pub median: Duration,
+}
+
+fn nanos<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+impl PhaseSummary {
+    /// Summarises `samples`, which must not be empty.
+    pub fn of(day: u8, phase: Phase, samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        PhaseSummary {
+            day,
+            phase,
+            runs: sorted.len(),
+            min: sorted[0],
+            median: sorted[(sorted.len() - 1) / 2],
+        }
+    }
+}
+
+impl fmt::Display for PhaseSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {:02} {:<6}  min {:>10.1?}  median {:>10.1?}  ({} run{})",
+            self.day,
+            self.phase,
+            self.min,
+            self.median,
+            self.runs,
+            if self.runs == 1 { "" } else { "s" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    #[test]
+    fn summaries_report_min_and_lower_median() {
+        let samples = [5, 1, 4, 2].map(Duration::from_micros);
+        let summary = PhaseSummary::of(1, Phase::Parse, &samples);
+        assert_eq!(summary.min, Duration::from_micros(1));
+        assert_eq!(summary.median, Duration::from_micros(2));
+        assert_eq!(summary.runs, 4);
+    }
+
+    #[test]
+    fn phases_list_parsing_then_each_answer() {
+        let ms = Duration::from_millis;
+        let solved = Solved {
+            parse: ms(3),
+            answers: vec![Answer {
+                part: Part::Two,
+                value: "12".to_string(),
+                elapsed: ms(1),
+            }],
+        };
+        assert_eq!(
+            solved.phases(),
+            vec![(Phase::Parse, ms(3)), (Phase::PartTwo, ms(1))]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use aoc_core::Part;
+use serde::Deserialize;
+
+/// The checked-in answer database, next to the workspace manifest.
+pub const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+/// One known answer as written in `answers.toml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    day: u8,
+    part: u8,
+    /// [`aoc_core::input_hash`] of the input, as 16 hex digits.
+    input_hash: String,
+    answer: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnswersFile {
+    #[serde(default)]
+    answer: Vec<Entry>,
+}
+
+/// Known answers keyed by day, part and input hash.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Answers(BTreeMap<(u8, u8, String), String>);
+
+impl Answers {
+    /// Parses an answer database, rejecting the same key listed twice.
+    ///
+    /// ```toml
+    /// [[answer]]
+    /// day = 1
+    /// part = 1
+    /// input_hash = "00f51b65d52f8c29"
+    /// answer = "24000"
+    /// ```
+    pub fn parse(contents: &str) -> Result<Self> {
+        let file: AnswersFile = toml::from_str(contents)?;
+        let mut answers = BTreeMap::new();
+        for entry in file.answer {
+            let key = (entry.day, entry.part, entry.input_hash);
+            if answers.insert(key.clone(), entry.answer).is_some() {
+                let (day, part, hash) = key;
+                bail!("Day {day} part {part} is listed twice for input {hash}");
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Invalid answers in {}", path.display()))
+    }
+
+    /// Compares `answer` with the one recorded for this day, part and input, if any.
+    pub fn check(&self, day: u8, part: Part, hash: &str, answer: &str) -> Verdict {
+        match self.0.get(&(day, part.number(), hash.to_string())) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+/// How a fresh answer compares with the recorded one.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// Nothing is recorded for this input yet.
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => f.write_str("pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {expected})"),
+            Verdict::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_are_keyed_by_day_part_and_input() -> Result<()> {
+        let answers = Answers::parse(
+            r#"
+            [[answer]]
+            day = 2
+            part = 1
+            input_hash = "cb49de7989531fb8"
+            answer = "15"
+            "#,
+        )?;
+        let hash = "cb49de7989531fb8";
+        assert_eq!(answers.check(2, Part::One, hash, "15"), Verdict::Pass);
+        assert_eq!(
+            answers.check(2, Part::One, hash, "16"),
+            Verdict::Fail {
+                expected: "15".to_string()
+            }
+        );
+        assert_eq!(answers.check(2, Part::Two, hash, "12"), Verdict::Unknown);
+        assert_eq!(
+            answers.check(2, Part::One, "0000000000000000", "15"),
+            Verdict::Unknown
+        );
+
+        let twice = "[[answer]]\nday = 1\npart = 1\ninput_hash = \"a\"\nanswer = \"1\"\n";
+        assert!(Answers::parse(&twice.repeat(2)).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use aoc_core::{
+    Answer, Format, InputSource, ParseMode, Part, Phase, PhaseSummary, Record, Solution, Solved,
+    input_hash,
+};
+use clap::{Parser, Subcommand};
+use day_01::Day01;
+use day_02::Day02;
+
+mod answers;
+
+use answers::{Answers, DEFAULT_ANSWERS, Verdict};
+
+/// Runs the Advent of Code 2022 solutions from a single binary.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single day, or every day with `--all`.
+    Run {
+        /// Day number to run (e.g. `1`).
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+        /// Only run the given part.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Run every registered day.
+        #[arg(long)]
+        all: bool,
+        /// Input file for a single day (`-` for stdin); defaults to `AOC_INPUT`, then the day's `input.txt`.
+        #[arg(long, conflicts_with = "all")]
+        input: Option<String>,
+        /// How to treat irregular input layout: lenient or strict.
+        #[arg(long, default_value = "lenient")]
+        mode: ParseMode,
+        /// Output format: json (one record per answer, for automation) or text.
+        #[arg(long, default_value = "json")]
+        format: Format,
+        /// Also time reading, parsing and each part, separately.
+        #[arg(long)]
+        time: bool,
+        /// Time every phase this many times and report the fastest and median run; implies `--time`.
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        repeat: Option<u32>,
+    },
+    /// Answer every day from its committed example and from its own input, and check each
+    /// answer against the recorded one; only a mismatch fails, while a missing input counts
+    /// as unknown.
+    Verify {
+        /// Answer database keyed by day, part and input hash.
+        #[arg(long, default_value = DEFAULT_ANSWERS)]
+        answers: PathBuf,
+        /// How to treat irregular input layout: lenient or strict.
+        #[arg(long, default_value = "lenient")]
+        mode: ParseMode,
+    },
+}
+
+/// The interface every registered day exposes to the runner.
+struct Day {
+    number: u8,
+    default_input: &'static str,
+    example_input: &'static str,
+    solve: fn(&str, &[Part], ParseMode) -> Result<Solved>,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Day {
+            number: S::DAY,
+            default_input: S::DEFAULT_INPUT,
+            example_input: S::EXAMPLE_INPUT,
+            solve: aoc_core::solve::<S>,
+        }
+    }
+}
+
+/// Every day the runner knows about, in order.
+const DAYS: &[Day] = &[Day::of::<Day01>(), Day::of::<Day02>()];
+
+/// How `aoc run` answers each day.
+struct RunOptions<'a> {
+    parts: &'a [Part],
+    mode: ParseMode,
+    format: Format,
+    /// How many times to time each phase, if at all.
+    timed_runs: Option<u32>,
+}
+
+fn run_day(day: &Day, source: &InputSource, options: &RunOptions) -> Result<()> {
+    let start = Instant::now();
+    let input = source.read()?;
+    let read = start.elapsed();
+    let hash = input_hash(input.as_bytes());
+
+    let solved = (day.solve)(&input, options.parts, options.mode)
+        .with_context(|| format!("Day {} failed", day.number))?;
+    for answer in solved.answers {
+        let part = answer.part;
+        match options.format {
+            Format::Text => println!(
+                "Day {:02} part {}: {}",
+                day.number,
+                part.number(),
+                answer.value
+            ),
+            Format::Json => {
+                // Parsing is shared by every part, so each record counts it on top of its own time.
+                let elapsed = solved.parse + answer.elapsed;
+                let record = Record::new(day.number, part, answer.value, elapsed, hash);
+                println!("{}", serde_json::to_string(&record)?);
+            }
+        }
+    }
+
+    if let Some(runs) = options.timed_runs {
+        for summary in time_day(day, source, &input, read, options, runs)? {
+            match options.format {
+                Format::Text => println!("{summary}"),
+                Format::Json => println!("{}", serde_json::to_string(&summary)?),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Times every phase of `day` `runs` times, starting from the `read` that produced `input`.
+///
+/// Stdin can only be read once, so that first read is its only read sample.
+fn time_day(
+    day: &Day,
+    source: &InputSource,
+    input: &str,
+    read: Duration,
+    options: &RunOptions,
+    runs: u32,
+) -> Result<Vec<PhaseSummary>> {
+    let mut samples: Vec<(Phase, Vec<Duration>)> = Vec::new();
+    for run in 0..runs {
+        let mut timings = Vec::new();
+        if run == 0 {
+            timings.push((Phase::Read, read));
+        } else if let InputSource::File(_) = source {
+            let start = Instant::now();
+            source.read()?;
+            timings.push((Phase::Read, start.elapsed()));
+        }
+        timings.extend((day.solve)(input, options.parts, options.mode)?.phases());
+        for (phase, elapsed) in timings {
+            match samples.iter_mut().find(|(p, _)| *p == phase) {
+                Some((_, durations)) => durations.push(elapsed),
+                None => samples.push((phase, vec![elapsed])),
+            }
+        }
+    }
+    Ok(samples
+        .iter()
+        .map(|(phase, durations)| PhaseSummary::of(day.number, *phase, durations))
+        .collect())
+}
+
+/// Checks every part of every day against `answers`, failing if any answer changed.
+fn verify(answers: &Answers, mode: ParseMode) -> Result<()> {
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+    for day in DAYS {
+        for (label, path) in [("example", day.example_input), ("input", day.default_input)] {
+            let input = match InputSource::File(path.into()).read() {
+                Ok(input) => input,
+                Err(err) => {
+                    // A missing input leaves nothing to check, which isn't a mismatch.
+                    for part in Part::ALL {
+                        println!(
+                            "Day {:02} part {} {label}: {} ({err:#})",
+                            day.number,
+                            part.number(),
+                            Verdict::Unknown
+                        );
+                        unknown += 1;
+                    }
+                    continue;
+                }
+            };
+            let hash = format!("{:016x}", input_hash(input.as_bytes()));
+            let solved = (day.solve)(&input, &Part::ALL, mode)
+                .with_context(|| format!("Day {} failed on its {label}", day.number))?;
+            for Answer {
+                part,
+                value: answer,
+                ..
+            } in solved.answers
+            {
+                let verdict = answers.check(day.number, part, &hash, &answer);
+                print!(
+                    "Day {:02} part {} {label}: {answer} {verdict}",
+                    day.number,
+                    part.number()
+                );
+                match verdict {
+                    Verdict::Pass => passed += 1,
+                    Verdict::Fail { .. } => failed += 1,
+                    Verdict::Unknown => {
+                        unknown += 1;
+                        print!(" (nothing recorded for input {hash})");
+                    }
+                }
+                println!();
+            }
+        }
+    }
+    println!("{passed} passed, {failed} failed, {unknown} unknown");
+    if failed > 0 {
+        bail!("{failed} answer(s) no longer match the recorded ones");
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            all,
+            input,
+            mode,
+            format,
+            time,
+            repeat,
+        } => {
+            let parts: &[Part] = match part {
+                Some(1) => &[Part::One],
+                Some(_) => &[Part::Two],
+                None => &Part::ALL,
+            };
+            let options = RunOptions {
+                parts,
+                mode,
+                format,
+                timed_runs: repeat.or(time.then_some(1)),
+            };
+
+            if all {
+                // Each day reads its own default input; a single override can't apply to all of them.
+                for day in DAYS {
+                    run_day(day, &InputSource::File(day.default_input.into()), &options)?;
+                }
+            } else {
+                // clap guarantees a day number when `--all` is absent.
+                let number = day.context("Missing day number")?;
+                let Some(day) = DAYS.iter().find(|d| d.number == number) else {
+                    bail!("Day {number} is not implemented");
+                };
+                let source = InputSource::resolve(input.as_deref(), day.default_input);
+                run_day(day, &source, &options)?;
+            }
+        }
+        Command::Verify { answers, mode } => verify(&Answers::load(&answers)?, mode)?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::hint::black_box;
+
+use aoc_core::Solution;
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use day_01::{Day01, parse_elf_calories, part_one, part_two};
+use rand::rngs::ChaCha8Rng;
+use rand::{RngExt, SeedableRng};
+
+/// A calorie list of `elves` blocks of 1-14 items each, the same on every run.
+fn generated(elves: usize, line_ending: &str) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(2022);
+    let mut input = String::new();
+    for _ in 0..elves {
+        for _ in 0..rng.random_range(1..=14) {
+            let calories: u32 = rng.random_range(1000..60_000);
+            input += &format!("{calories}{line_ending}");
+        }
+        input += line_ending;
+    }
+    input
+}
+
+/// The real puzzle input if it's there, then large generated inputs with both line endings.
+fn inputs() -> Vec<(&'static str, String)> {
+    let mut inputs = Vec::new();
+    if let Ok(real) = fs::read_to_string(Day01::DEFAULT_INPUT) {
+        inputs.push(("real", real));
+    }
+    inputs.push(("generated", generated(10_000, "\n")));
+    inputs.push(("generated-crlf", generated(10_000, "\r\n")));
+    inputs
+}
+
+fn bench(c: &mut Criterion) {
+    for (name, input) in inputs() {
+        let mut group = c.benchmark_group(format!("day01/{name}"));
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_function("parse_elf_calories", |b| {
+            b.iter(|| parse_elf_calories(black_box(&input)))
+        });
+        let calories = parse_elf_calories(&input).expect("benchmark input parses");
+        group.bench_function("part_one", |b| b.iter(|| part_one(black_box(&calories))));
+        group.bench_function("part_two", |b| b.iter(|| part_two(black_box(&calories))));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,160 @@
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+/// An unsigned integer type that elves' calorie totals can be accumulated in.
+pub trait Calories: Copy + Ord + Default + Display + Debug + Into<u128> + 'static {
+    /// Type name used in error messages, e.g. `"u32"`.
+    const NAME: &'static str;
+    /// Largest representable total.
+    const MAX: Self;
+
+    /// Converts a single item, failing if it doesn't fit.
+    fn from_item(item: u64) -> Option<Self>;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn saturating_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_calories {
+    ($($t:ident),*) => {
+        $(
+            impl Calories for $t {
+                const NAME: &'static str = stringify!($t);
+                const MAX: Self = $t::MAX;
+
+                fn from_item(item: u64) -> Option<Self> {
+                    $t::try_from(item).ok()
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    $t::checked_add(self, rhs)
+                }
+
+                fn saturating_add(self, rhs: Self) -> Self {
+                    $t::saturating_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_calories!(u32, u64, u128);
+
+/// What to do when an elf's total no longer fits the accumulator.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OverflowPolicy {
+    /// Report a [`ParseError::Overflow`](crate::ParseError::Overflow).
+    #[default]
+    Error,
+    /// Clamp the total at the accumulator's maximum.
+    Saturate,
+    /// Re-parse with the next wider accumulator. Streams can't be re-read, so [`Elves`] and
+    /// [`ElfTotals`] treat this like `Error`; see [`parse_totals`].
+    ///
+    /// [`Elves`]: crate::Elves
+    /// [`ElfTotals`]: crate::ElfTotals
+    /// [`parse_totals`]: crate::parse_totals
+    Promote,
+}
+
+impl OverflowPolicy {
+    /// The width and policy a single pass needs to accept what `self` accepts at `width`.
+    ///
+    /// Promoting may widen all the way, so a stream reads it as erroring only past `u128`.
+    pub fn in_one_pass(self, width: Width) -> (Width, OverflowPolicy) {
+        match self {
+            OverflowPolicy::Promote => (Width::U128, OverflowPolicy::Error),
+            policy => (width, policy),
+        }
+    }
+}
+
+impl FromStr for OverflowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(OverflowPolicy::Error),
+            "saturate" => Ok(OverflowPolicy::Saturate),
+            "promote" => Ok(OverflowPolicy::Promote),
+            other => Err(format!(
+                "unknown overflow policy {other:?}, expected error, saturate or promote"
+            )),
+        }
+    }
+}
+
+/// Accumulator widths, narrowest first.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub enum Width {
+    #[default]
+    U32,
+    U64,
+    U128,
+}
+
+impl Width {
+    /// The next wider accumulator, if there is one.
+    pub fn wider(self) -> Option<Width> {
+        match self {
+            Width::U32 => Some(Width::U64),
+            Width::U64 => Some(Width::U128),
+            Width::U128 => None,
+        }
+    }
+}
+
+impl FromStr for Width {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "u32" => Ok(Width::U32),
+            "u64" => Ok(Width::U64),
+            "u128" => Ok(Width::U128),
+            other => Err(format!(
+                "unknown width {other:?}, expected u32, u64 or u128"
+            )),
+        }
+    }
+}
+
+/// Per-elf totals in whichever width they were parsed with.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Totals {
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+    U128(Vec<u128>),
+}
+
+impl Totals {
+    pub fn width(&self) -> Width {
+        match self {
+            Totals::U32(_) => Width::U32,
+            Totals::U64(_) => Width::U64,
+            Totals::U128(_) => Width::U128,
+        }
+    }
+
+    /// Part 1 answer, widened so it never overflows.
+    pub fn part_one(&self) -> u128 {
+        self.widened().max().unwrap_or(0)
+    }
+
+    /// Part 2 answer, summed in `u128` so adding the top three never overflows.
+    pub fn part_two(&self) -> u128 {
+        crate::top_n(self.widened(), 3)
+            .iter()
+            .map(|elf| elf.calories)
+            .sum()
+    }
+
+    fn widened(&self) -> Box<dyn Iterator<Item = u128> + '_> {
+        match self {
+            Totals::U32(totals) => Box::new(totals.iter().map(|&t| t.into())),
+            Totals::U64(totals) => Box::new(totals.iter().map(|&t| t.into())),
+            Totals::U128(totals) => Box::new(totals.iter().copied()),
+        }
+    }
+}
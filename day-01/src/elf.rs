@@ -0,0 +1,64 @@
+use crate::calories::Calories;
+
+/// One elf's inventory, as read from its block of the input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Elf<T = u64> {
+    /// 0-based position of the elf in the input.
+    pub index: usize,
+    /// Calories of each item, in input order.
+    pub items: Vec<u64>,
+    /// Sum of `items`, unless it was saturated at `T::MAX`.
+    pub total: T,
+}
+
+impl<T> Elf<T> {
+    /// The most calorific single item the elf carries.
+    pub fn largest_item(&self) -> Option<u64> {
+        self.items.iter().copied().max()
+    }
+}
+
+/// The standout elves of an input, gathered in a single pass.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Highlights<T = u64> {
+    /// The elf carrying the most calories.
+    pub most_calories: Elf<T>,
+    /// The elf carrying the most items.
+    pub most_items: Elf<T>,
+    /// The elf carrying the largest single item.
+    pub largest_item: Elf<T>,
+}
+
+/// Finds the [`Highlights`] of a stream of elves, stopping at the first error.
+///
+/// Only the current leaders are kept, so this runs in memory proportional to their inventories.
+/// Ties go to the elf that appears first. Returns `None` if there are no elves.
+pub fn highlights<T: Calories, E>(
+    elves: impl IntoIterator<Item = Result<Elf<T>, E>>,
+) -> Result<Option<Highlights<T>>, E> {
+    let mut best: Option<Highlights<T>> = None;
+
+    for elf in elves {
+        let elf = elf?;
+        let Some(best) = best.as_mut() else {
+            best = Some(Highlights {
+                most_calories: elf.clone(),
+                most_items: elf.clone(),
+                largest_item: elf,
+            });
+            continue;
+        };
+
+        if elf.total > best.most_calories.total {
+            best.most_calories = elf.clone();
+        }
+        if elf.items.len() > best.most_items.items.len() {
+            best.most_items = elf.clone();
+        }
+        if elf.largest_item() > best.largest_item.largest_item() {
+            best.largest_item = elf;
+        }
+    }
+
+    Ok(best)
+}
@@ -0,0 +1,65 @@
+use aoc_core::{Diagnostic, Irregularity, Snippet};
+use thiserror::Error;
+
+/// What a non-blank line of the calorie list must look like.
+pub const EXPECTED_CALORIES: &str = "a non-negative integer (digits 0-9)";
+
+/// Why the calorie list could not be parsed.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ParseError {
+    /// A line that is not a calorie count.
+    #[error("line {line}: expected {expected}, found {token:?}\n{snippet}")]
+    InvalidNumber {
+        line: usize,
+        token: String,
+        expected: &'static str,
+        snippet: Snippet,
+    },
+    /// Adding `token` pushed an elf's total past what the accumulator can hold.
+    #[error("line {line}: calorie total overflows {width} when adding {token}\n{snippet}")]
+    Overflow {
+        line: usize,
+        token: String,
+        /// Name of the accumulator type, e.g. `"u32"`.
+        width: &'static str,
+        snippet: Snippet,
+    },
+    /// Layout that [`ParseMode::Strict`](aoc_core::ParseMode::Strict) rejects.
+    #[error("line {line}: {irregularity} is not allowed in strict mode\n{snippet}")]
+    Irregular {
+        line: usize,
+        irregularity: Irregularity,
+        snippet: Snippet,
+    },
+    /// The input contains no calorie counts at all.
+    #[error("no calorie blocks found in input")]
+    Empty,
+    /// The input could not be read past line `line` (e.g. it is not valid UTF-8).
+    #[error("line {line}: failed to read input: {message}")]
+    Io { line: usize, message: String },
+}
+
+impl ParseError {
+    /// The 1-based line the error points at, if it is tied to a line.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParseError::InvalidNumber { line, .. }
+            | ParseError::Overflow { line, .. }
+            | ParseError::Irregular { line, .. }
+            | ParseError::Io { line, .. } => Some(*line),
+            ParseError::Empty => None,
+        }
+    }
+}
+
+impl Diagnostic for ParseError {
+    fn kind(&self) -> &'static str {
+        match self {
+            ParseError::InvalidNumber { .. } => "malformed line",
+            ParseError::Overflow { .. } => "overflowed block",
+            ParseError::Irregular { .. } => "irregular line",
+            ParseError::Empty => "empty input",
+            ParseError::Io { .. } => "unreadable line",
+        }
+    }
+}
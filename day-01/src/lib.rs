@@ -0,0 +1,516 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::convert::Infallible;
+use std::io::BufRead;
+
+use anyhow::Result;
+use aoc_core::{Diagnostics, ParseMode, Solution};
+
+pub mod calories;
+pub mod elf;
+pub mod error;
+pub mod stats;
+pub mod stream;
+
+pub use calories::{Calories, OverflowPolicy, Totals, Width};
+pub use elf::{Elf, Highlights, highlights};
+pub use error::{EXPECTED_CALORIES, ParseError};
+pub use stats::{Stats, stats};
+pub use stream::{ElfTotals, Elves};
+
+/// Parses the input into a vector of total calories per elf.
+///
+/// This is the `u32` totals view of [`parse_elves`]. Elves are separated by one or more empty
+/// lines; the first problem found is reported with its line number. Use
+/// [`diagnose_elf_calories`] to see every problem at once, or [`ElfTotals`] to avoid holding the
+/// whole input in memory.
+pub fn parse_elf_calories(input: &str) -> Result<Vec<u32>, ParseError> {
+    ElfTotals::new(input.as_bytes()).collect()
+}
+
+/// Parses per-elf totals accumulated in `T` in `mode`, handling overflow according to `policy`.
+///
+/// [`OverflowPolicy::Promote`] can't change `T`, so it fails like `Error`; use
+/// [`parse_totals`] to widen automatically.
+pub fn parse_calories<T: Calories>(
+    input: &str,
+    policy: OverflowPolicy,
+    mode: ParseMode,
+) -> Result<Vec<T>, ParseError> {
+    ElfTotals::with_policy(input.as_bytes(), policy)
+        .mode(mode)
+        .collect()
+}
+
+/// Parses per-elf totals in `mode`, starting at `width`.
+///
+/// With [`OverflowPolicy::Promote`], an overflow re-parses the input with the next wider type
+/// until the totals fit or `u128` overflows too.
+pub fn parse_totals(
+    input: &str,
+    width: Width,
+    policy: OverflowPolicy,
+    mode: ParseMode,
+) -> Result<Totals, ParseError> {
+    let parsed = match width {
+        Width::U32 => parse_calories(input, policy, mode).map(Totals::U32),
+        Width::U64 => parse_calories(input, policy, mode).map(Totals::U64),
+        Width::U128 => parse_calories(input, policy, mode).map(Totals::U128),
+    };
+    match (parsed, policy, width.wider()) {
+        (Err(ParseError::Overflow { .. }), OverflowPolicy::Promote, Some(wider)) => {
+            parse_totals(input, wider, policy, mode)
+        }
+        (parsed, _, _) => parsed,
+    }
+}
+
+/// Parses the input into per-elf records, keeping every item.
+pub fn parse_elves(input: &str) -> Result<Vec<Elf>, ParseError> {
+    Elves::new(input.as_bytes()).collect()
+}
+
+/// Validates the whole input in `mode`, collecting every malformed line and every block that
+/// overflows `width` under `policy`.
+pub fn diagnose_elf_calories(
+    reader: impl BufRead,
+    width: Width,
+    policy: OverflowPolicy,
+    mode: ParseMode,
+) -> Diagnostics<ParseError> {
+    fn errors<T: Calories>(
+        reader: impl BufRead,
+        policy: OverflowPolicy,
+        mode: ParseMode,
+    ) -> Vec<ParseError> {
+        ElfTotals::<_, T>::with_policy(reader, policy)
+            .mode(mode)
+            .filter_map(Result::err)
+            .collect()
+    }
+
+    let errors = match policy.in_one_pass(width) {
+        (Width::U32, policy) => errors::<u32>(reader, policy, mode),
+        (Width::U64, policy) => errors::<u64>(reader, policy, mode),
+        (Width::U128, policy) => errors::<u128>(reader, policy, mode),
+    };
+    Diagnostics { errors }
+}
+
+/// An elf selected by [`top_n`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RankedElf<T = u32> {
+    /// 0-based position of the elf in the input.
+    pub index: usize,
+    /// Total calories the elf carries.
+    pub calories: T,
+}
+
+/// Selects the `n` elves carrying the most calories, largest first.
+///
+/// Runs in O(len · log n) time and O(n) space; ties go to the elf that appears first.
+pub fn top_n<T: Ord>(calories: impl IntoIterator<Item = T>, n: usize) -> Vec<RankedElf<T>> {
+    match try_top_n(calories.into_iter().map(Ok::<_, Infallible>), n) {
+        Ok(top) => top,
+        Err(never) => match never {},
+    }
+}
+
+/// Like [`top_n`], but over fallible totals such as those from [`ElfTotals`], stopping at the
+/// first error.
+///
+/// Together with [`ElfTotals`] this ranks arbitrarily large inputs in constant memory.
+pub fn try_top_n<T: Ord, E>(
+    calories: impl IntoIterator<Item = Result<T, E>>,
+    n: usize,
+) -> Result<Vec<RankedElf<T>>, E> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Min-heap of the best `n` seen so far, so the weakest candidate is the one evicted.
+    // Ordering by `Reverse(index)` second makes the later elf lose a tie.
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    for (index, total) in calories.into_iter().enumerate() {
+        heap.push(Reverse((total?, Reverse(index))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+
+    // Sorting the reversed keys ascending yields the elves in descending order.
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((calories, Reverse(index)))| RankedElf { index, calories })
+        .collect())
+}
+
+/// Part 1: find the maximum calories carried by any single elf.
+pub fn part_one<T: Calories>(calories: &[T]) -> T {
+    top_n(calories.iter().copied(), 1)
+        .first()
+        .map_or(T::default(), |elf| elf.calories)
+}
+
+/// Part 2: find the sum of the top three calorie totals.
+///
+/// The sum saturates at `T::MAX`; [`Totals::part_two`] sums in `u128` instead.
+pub fn part_two<T: Calories>(calories: &[T]) -> T {
+    top_n(calories.iter().copied(), 3)
+        .iter()
+        .fold(T::default(), |sum, elf| sum.saturating_add(elf.calories))
+}
+
+/// Day 1: Calorie Counting.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const DEFAULT_INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+    const EXAMPLE_INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt");
+
+    type Input = Vec<u32>;
+    type PartOne = u32;
+    /// Three `u32` totals can overflow `u32`, so they are summed in `u64`.
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, mode: ParseMode) -> Result<Self::Input> {
+        Ok(ElfTotals::new(input.as_bytes())
+            .mode(mode)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_one(calories: &Self::Input) -> Result<u32> {
+        Ok(part_one(calories))
+    }
+
+    fn part_two(calories: &Self::Input) -> Result<u64> {
+        Ok(top_n(calories.iter().copied(), 3)
+            .iter()
+            .map(|elf| u64::from(elf.calories))
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Irregularity;
+    use proptest::prelude::*;
+
+    #[test]
+    fn sample_calorie_parsing() -> Result<()> {
+        let sample = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+        let got = parse_elf_calories(sample)?;
+        assert_eq!(got, vec![6000, 4000, 11000, 24000, 10000]);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_number_reports_line() {
+        let err = parse_elf_calories("1000\r\n2000\r\n\r\n  3x00\r\n").unwrap_err();
+        assert_eq!(err.line(), Some(4));
+        assert_eq!(
+            err.to_string(),
+            "line 4: expected a non-negative integer (digits 0-9), found \"3x00\"\n  |\n4 |   3x00\n  |   ^^^^"
+        );
+        assert_eq!(parse_elf_calories("\n\n"), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn diagnostics_collect_every_problem() {
+        let input = "1\nx\n\n4294967295\n1\n2\n\ny\n";
+        let report = diagnose_elf_calories(
+            input.as_bytes(),
+            Width::U32,
+            OverflowPolicy::Error,
+            ParseMode::Lenient,
+        );
+        let lines: Vec<_> = report.errors.iter().filter_map(ParseError::line).collect();
+        assert_eq!(lines, vec![2, 5, 8]);
+        assert_eq!(
+            report.summary(),
+            vec![("malformed line", 2), ("overflowed block", 1)]
+        );
+        // The normal API still stops at the first one.
+        assert_eq!(parse_elf_calories(input).unwrap_err().line(), Some(2));
+
+        // A wider accumulator, or promoting to one, leaves only the malformed lines.
+        for (width, policy) in [
+            (Width::U64, OverflowPolicy::Error),
+            (Width::U32, OverflowPolicy::Promote),
+        ] {
+            let report = diagnose_elf_calories(input.as_bytes(), width, policy, ParseMode::Lenient);
+            let lines: Vec<_> = report.errors.iter().filter_map(ParseError::line).collect();
+            assert_eq!(lines, vec![2, 8]);
+        }
+    }
+
+    #[test]
+    fn strict_mode_rejects_what_lenient_mode_skips() -> Result<()> {
+        let input = "1\n+2\n\n\n3 \n\n4\n";
+        assert_eq!(Day01::parse(input)?, vec![3, 3, 4]);
+
+        let report = diagnose_elf_calories(
+            input.as_bytes(),
+            Width::U32,
+            OverflowPolicy::Error,
+            ParseMode::Strict,
+        );
+        let found: Vec<_> = report
+            .errors
+            .iter()
+            .map(|err| match err {
+                ParseError::Irregular {
+                    line, irregularity, ..
+                } => (*line, *irregularity),
+                other => panic!("unexpected error {other}"),
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, Irregularity::LeadingPlus),
+                (4, Irregularity::ExtraBlankLine),
+                (5, Irregularity::StrayWhitespace),
+            ]
+        );
+        assert_eq!(
+            Day01::parse_with("1\n\n2\n", ParseMode::Strict)?,
+            vec![1, 2]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sample_top_three_sum() -> Result<()> {
+        let sample = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+        let calories = parse_elf_calories(sample)?;
+        let got = part_two(&calories);
+        assert_eq!(got, 45000);
+        Ok(())
+    }
+
+    #[test]
+    fn solution_part_two_does_not_overflow_u32() -> Result<()> {
+        let calories = Day01::parse("2000000000\n\n2000000000\n\n2000000000\n")?;
+        assert_eq!(Day01::part_two(&calories)?, 6_000_000_000);
+        Ok(())
+    }
+
+    #[test]
+    fn top_n_keeps_indices_and_breaks_ties_by_position() {
+        let calories = [6000, 4000, 11000, 24000, 10000, 11000];
+        let got = top_n(calories, 3);
+        assert_eq!(
+            got,
+            vec![
+                RankedElf {
+                    index: 3,
+                    calories: 24000
+                },
+                RankedElf {
+                    index: 2,
+                    calories: 11000
+                },
+                RankedElf {
+                    index: 5,
+                    calories: 11000
+                },
+            ]
+        );
+        assert!(top_n(calories, 0).is_empty());
+        assert_eq!(top_n(calories, 10).len(), calories.len());
+    }
+
+    #[test]
+    fn streaming_crlf_matches_lf() -> Result<()> {
+        let lf = "1000\n2000\n\n\n4000\n\n5000\n6000";
+        let crlf = lf.replace('\n', "\r\n");
+
+        let from_lf: Vec<u32> = ElfTotals::new(lf.as_bytes()).collect::<Result<_, _>>()?;
+        let from_crlf: Vec<u32> = ElfTotals::new(crlf.as_bytes()).collect::<Result<_, _>>()?;
+        assert_eq!(from_lf, vec![3000, 4000, 11000]);
+        assert_eq!(from_crlf, from_lf);
+
+        let top = try_top_n(ElfTotals::new(crlf.as_bytes()), 1)?;
+        assert_eq!(
+            top,
+            vec![RankedElf {
+                index: 2,
+                calories: 11000
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn elf_records_keep_items_and_positions() -> Result<()> {
+        let err = parse_elves("100\n200\n\nbad\n").unwrap_err();
+        assert_eq!(err.line(), Some(4));
+
+        let elves = parse_elves("100\n200\n\n5000\n\n\n1\n2\n3\n")?;
+        assert_eq!(elves[2].index, 2);
+        assert_eq!(elves[2].items, vec![1, 2, 3]);
+
+        let best = highlights(elves.into_iter().map(Ok::<_, ParseError>))?.unwrap();
+        assert_eq!(best.most_calories.index, 1);
+        assert_eq!(best.most_items.index, 2);
+        assert_eq!(best.largest_item.largest_item(), Some(5000));
+        Ok(())
+    }
+
+    #[test]
+    fn overflow_policy_error() {
+        let input = "4294967295\n1\n\n5\n";
+        let err =
+            parse_calories::<u32>(input, OverflowPolicy::Error, ParseMode::Lenient).unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::Overflow {
+                line: 2,
+                width: "u32",
+                ..
+            }
+        ));
+        // A single item too large for the accumulator overflows too.
+        let err = parse_calories::<u32>("4294967296\n", OverflowPolicy::Error, ParseMode::Lenient)
+            .unwrap_err();
+        assert!(matches!(err, ParseError::Overflow { line: 1, .. }));
+    }
+
+    #[test]
+    fn overflow_policy_saturate() -> Result<()> {
+        let input = "4294967295\n1\n\n5\n";
+        let totals = parse_calories::<u32>(input, OverflowPolicy::Saturate, ParseMode::Lenient)?;
+        assert_eq!(totals, vec![u32::MAX, 5]);
+        assert_eq!(part_two(&totals), u32::MAX);
+        assert_eq!(
+            parse_calories::<u64>(input, OverflowPolicy::Error, ParseMode::Lenient)?,
+            vec![4294967296, 5]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn overflow_policy_promote() -> Result<()> {
+        let input = "4294967295\n1\n\n5\n";
+        let totals = parse_totals(
+            input,
+            Width::U32,
+            OverflowPolicy::Promote,
+            ParseMode::Lenient,
+        )?;
+        assert_eq!(totals, Totals::U64(vec![4294967296, 5]));
+        assert_eq!(totals.part_one(), 4294967296);
+
+        let huge = format!("{0}\n{0}\n", u64::MAX);
+        let totals = parse_totals(
+            &huge,
+            Width::U32,
+            OverflowPolicy::Promote,
+            ParseMode::Lenient,
+        )?;
+        assert_eq!(totals.width(), Width::U128);
+        assert_eq!(totals.part_two(), 2 * u128::from(u64::MAX));
+
+        // Inputs that fit stay in the requested width.
+        let totals = parse_totals(
+            "1\n2\n",
+            Width::U32,
+            OverflowPolicy::Promote,
+            ParseMode::Lenient,
+        )?;
+        assert_eq!(totals, Totals::U32(vec![3]));
+        Ok(())
+    }
+
+    #[test]
+    fn sample_stats() -> Result<()> {
+        let calories = parse_elf_calories(
+            "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+        )?;
+        let report = stats(&calories, 2).unwrap();
+        assert_eq!((report.count, report.min, report.max), (5, 4000, 24000));
+        assert_eq!((report.mean, report.median), (11000.0, 10000.0));
+        assert_eq!(report.percentiles[1].value, 6000.0);
+        let counts: Vec<_> = report
+            .histogram
+            .iter()
+            .map(|bin| (bin.start, bin.end, bin.count))
+            .collect();
+        assert_eq!(counts, vec![(4000, 14000, 4), (14001, 24000, 1)]);
+        assert!(stats::<u32>(&[], 10).is_none());
+        Ok(())
+    }
+
+    /// Renders `blocks` as a calorie list, with `gap` blank lines between blocks.
+    fn render(blocks: &[Vec<u32>], gap: usize, line_ending: &str) -> String {
+        blocks
+            .iter()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| format!("{item}{line_ending}"))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(&line_ending.repeat(gap))
+    }
+
+    proptest! {
+        #[test]
+        fn parsing_keeps_every_block_whatever_the_layout(
+            blocks in prop::collection::vec(prop::collection::vec(0u32..100_000, 1..8), 1..20),
+            gap in 1usize..4,
+        ) {
+            let lf = parse_elf_calories(&render(&blocks, gap, "\n")).unwrap();
+            let crlf = parse_elf_calories(&render(&blocks, gap, "\r\n")).unwrap();
+            prop_assert_eq!(&lf, &crlf);
+            prop_assert_eq!(lf.len(), blocks.len());
+            let totals: Vec<u32> = blocks.iter().map(|items| items.iter().sum()).collect();
+            prop_assert_eq!(&lf, &totals);
+        }
+
+        #[test]
+        fn part_one_is_the_max_and_part_two_adds_to_it(
+            calories in prop::collection::vec(0u32..1_000_000, 1..50),
+        ) {
+            prop_assert_eq!(part_one(&calories), *calories.iter().max().unwrap());
+            prop_assert!(part_two(&calories) >= part_one(&calories));
+        }
+    }
+}
@@ -1,118 +1,197 @@
-use anyhow::{Context, Result, bail};
-
-/// Parses the input into a vector of total calories per elf.
-pub fn parse_elf_calories(input: &str) -> Result<Vec<u32>> {
-    // Normalize Windows line endings so splitting on "\n\n" is reliable.
-    let normalized = input.replace("\r\n", "\n");
-
-    let calories = normalized
-        // Drop trailing whitespace/newlines so we don't accidentally create an extra empty "block".
-        .trim_end()
-        // Split the input into blocks separated by blank lines.
-        .split("\n\n")
-        // Map blocks to their summed calories, propagating parse/overflow errors with context.
-        .map(|block| -> Result<u32> {
-            block
-                // Split the block into lines.
-                .lines()
-                // Ignore empty/whitespace-only lines (defensive; blocks *shouldn't* contain these).
-                .filter(|line| !line.trim().is_empty())
-                // Parse each line as a u32, attaching a helpful error message on failure.
-                .map(|line| {
-                    line.trim()
-                        .parse::<u32>()
-                        .with_context(|| format!("Failed to parse number: {line:?}"))
-                })
-                // Sum the numbers, failing if the sum would overflow u32.
-                .try_fold(0u32, |acc, n| {
-                    let n = n?;
-                    acc.checked_add(n).context("Calories sum overflow")
-                })
-        })
-        .collect::<Result<Vec<_>>>()?;
-
-    if calories.is_empty() {
-        bail!("No calorie blocks found in input");
-    }
+use std::borrow::Cow;
+use std::io::BufRead;
+use std::time::Instant;
 
-    Ok(calories)
+use anyhow::{Context, Result, bail};
+use aoc_core::{Format, InputSource, ParseMode, Part, Record, Solution, input_hash};
+use clap::{Parser, Subcommand};
+use day_01::{
+    Calories, Day01, ElfTotals, Elves, OverflowPolicy, Totals, Width, diagnose_elf_calories,
+    highlights, parse_totals, stats, try_top_n,
+};
+
+/// Day 1: Calorie Counting.
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Puzzle input (`-` for stdin); defaults to `AOC_INPUT`, then `input.txt`.
+    input: Option<String>,
+    /// Validate the whole input and report every problem instead of solving.
+    #[arg(long)]
+    check: bool,
+    /// Describe the standout elves and print the winner's inventory instead of solving.
+    #[arg(long, conflicts_with = "check")]
+    details: bool,
+    /// Integer type to accumulate totals in: u32, u64 or u128.
+    #[arg(long, global = true, default_value = "u32")]
+    width: Width,
+    /// What to do when a total overflows: error, saturate, or promote to a wider type.
+    #[arg(long, global = true, default_value = "error")]
+    overflow: OverflowPolicy,
+    /// How to treat irregular layout such as tabs, `+` signs or runs of blank lines: lenient or strict.
+    #[arg(long, global = true, default_value = "lenient")]
+    mode: ParseMode,
+    /// Output format for the answers: text (one per line) or json (one record per part).
+    #[arg(long, default_value = "text", conflicts_with_all = ["check", "details"])]
+    format: Format,
 }
 
-/// Part 1: find the maximum calories carried by any single elf.
-pub fn part_one(calories: &[u32]) -> u32 {
-    *calories.iter().max().unwrap_or(&0)
+#[derive(Subcommand)]
+enum Command {
+    /// Report statistics and a histogram of the elves' calorie totals instead of solving.
+    Stats {
+        /// Puzzle input (`-` for stdin); defaults to `AOC_INPUT`, then `input.txt`.
+        input: Option<String>,
+        /// Output format: text or json.
+        #[arg(long, default_value = "text")]
+        format: Format,
+        /// Maximum number of histogram bins.
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+        bins: u16,
+    },
 }
 
-/// Part 2: find the sum of the top three calorie totals.
-pub fn part_two(calories: &[u32]) -> u32 {
-    let mut sorted = calories.to_vec();
-    sorted.sort_unstable_by(|a, b| b.cmp(a));
-    sorted.iter().take(3).copied().sum()
+/// Streams the input once, returning both answers widened to `u128`.
+fn stream_answers<T: Calories>(
+    reader: impl BufRead,
+    policy: OverflowPolicy,
+    mode: ParseMode,
+) -> Result<(u128, u128)> {
+    // The top three elves answer both parts: the first is part one, their sum is part two.
+    let top = try_top_n(ElfTotals::<_, T>::with_policy(reader, policy).mode(mode), 3)
+        .context("Failed to parse calories")?;
+    let part1 = top.first().map_or(0, |elf| elf.calories.into());
+    let part2 = top.iter().map(|elf| elf.calories.into()).sum();
+    Ok((part1, part2))
 }
 
-fn main() -> Result<()> {
-    // Embed the input file at compile time so the solution is a single binary with no runtime I/O.
-    let input = include_str!("../input.txt");
-
-    let calories = parse_elf_calories(input).context("Failed to parse calories")?;
-    let part1 = part_one(&calories);
-    let part2 = part_two(&calories);
-
-    println!("{part1}");
-    println!("{part2}");
+/// Describes the standout elves, with totals accumulated in `T`.
+fn print_details<T: Calories>(
+    reader: impl BufRead,
+    policy: OverflowPolicy,
+    mode: ParseMode,
+) -> Result<()> {
+    let best = highlights(Elves::<_, T>::with_policy(reader, policy).mode(mode))
+        .context("Failed to parse calories")?
+        .context("No elves found")?;
+    let inventory: Vec<String> = best
+        .most_calories
+        .items
+        .iter()
+        .map(u64::to_string)
+        .collect();
+    // Elves are numbered from 1 for people; `index` is 0-based.
+    println!(
+        "Most calories: elf #{} with {} ({})",
+        best.most_calories.index + 1,
+        best.most_calories.total,
+        inventory.join(" + ")
+    );
+    println!(
+        "Most items: elf #{} with {} items",
+        best.most_items.index + 1,
+        best.most_items.items.len()
+    );
+    println!(
+        "Largest item: elf #{} with {}",
+        best.largest_item.index + 1,
+        best.largest_item.largest_item().unwrap_or(0)
+    );
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn sample_calorie_parsing() -> Result<()> {
-        let sample = "\
-1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000
-";
-
-        let got = parse_elf_calories(sample)?;
-        assert_eq!(got, vec![6000, 4000, 11000, 24000, 10000]);
-        Ok(())
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    if let Some(Command::Stats {
+        input,
+        format,
+        bins,
+    }) = args.command
+    {
+        let source = InputSource::resolve(input.as_deref(), Day01::DEFAULT_INPUT);
+        let totals = parse_totals(&source.read()?, args.width, args.overflow, args.mode)
+            .context("Failed to parse calories")?;
+        let report = match &totals {
+            Totals::U32(calories) => stats(calories, bins.into()),
+            Totals::U64(calories) => stats(calories, bins.into()),
+            Totals::U128(calories) => stats(calories, bins.into()),
+        }
+        .context("No elves found")?;
+        match format {
+            Format::Text => print!("{report}"),
+            Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+        return Ok(());
     }
 
-    #[test]
-    fn sample_top_three_sum() -> Result<()> {
-        let sample = "\
-1000
-2000
-3000
-
-4000
+    // Read the input at runtime: an explicit path (or `-` for stdin), then `AOC_INPUT`, then `input.txt`.
+    let source = InputSource::resolve(args.input.as_deref(), Day01::DEFAULT_INPUT);
 
-5000
-6000
-
-7000
-8000
-9000
+    if args.check {
+        let report = diagnose_elf_calories(source.open()?, args.width, args.overflow, args.mode);
+        if !report.is_empty() {
+            bail!("{report}");
+        }
+        println!("{report}");
+        return Ok(());
+    }
 
-10000
-";
+    if args.details {
+        let reader = source.open()?;
+        return match args.overflow.in_one_pass(args.width) {
+            (Width::U32, policy) => print_details::<u32>(reader, policy, args.mode),
+            (Width::U64, policy) => print_details::<u64>(reader, policy, args.mode),
+            (Width::U128, policy) => print_details::<u128>(reader, policy, args.mode),
+        };
+    }
 
-        let calories = parse_elf_calories(sample)?;
-        let got = part_two(&calories);
-        assert_eq!(got, 45000);
-        Ok(())
+    // JSON records carry a hash of the input, so it's held in memory to be hashed first.
+    let held = match args.format {
+        Format::Json => Some(source.read()?),
+        Format::Text => None,
+    };
+    let open = || -> Result<Box<dyn BufRead + '_>> {
+        Ok(match &held {
+            Some(text) => Box::new(text.as_bytes()),
+            None => source.open()?,
+        })
+    };
+
+    // Stream the input line by line so arbitrarily large inputs run in constant memory,
+    // except when promoting: that may need a second pass, so the input is held in memory.
+    let start = Instant::now();
+    let (part1, part2) = match (args.overflow, args.width) {
+        (OverflowPolicy::Promote, width) => {
+            let text = match &held {
+                Some(text) => Cow::Borrowed(text),
+                None => Cow::Owned(source.read()?),
+            };
+            let totals = parse_totals(&text, width, OverflowPolicy::Promote, args.mode)
+                .context("Failed to parse calories")?;
+            (totals.part_one(), totals.part_two())
+        }
+        (policy, Width::U32) => stream_answers::<u32>(open()?, policy, args.mode)?,
+        (policy, Width::U64) => stream_answers::<u64>(open()?, policy, args.mode)?,
+        (policy, Width::U128) => stream_answers::<u128>(open()?, policy, args.mode)?,
+    };
+    let elapsed = start.elapsed();
+
+    match (args.format, &held) {
+        (Format::Json, Some(text)) => {
+            let hash = input_hash(text.as_bytes());
+            // Both parts come out of the same pass, so each record carries that pass's time.
+            for (part, answer) in [(Part::One, part1), (Part::Two, part2)] {
+                let record = Record::new(Day01::DAY, part, answer, elapsed, hash);
+                println!("{}", serde_json::to_string(&record)?);
+            }
+        }
+        _ => {
+            println!("{part1}");
+            println!("{part2}");
+        }
     }
+    Ok(())
 }
@@ -0,0 +1,150 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::calories::Calories;
+
+/// Percentile ranks included in every [`Stats`] report.
+pub const PERCENTILE_RANKS: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// Width in characters of the longest histogram bar.
+const BAR_WIDTH: usize = 40;
+
+/// Summary statistics of the elves' calorie totals.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Stats {
+    pub count: usize,
+    pub min: u128,
+    pub max: u128,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation.
+    pub std_dev: f64,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<Bin>,
+}
+
+/// The total below which `rank` percent of elves fall, linearly interpolated.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub struct Percentile {
+    pub rank: u8,
+    pub value: f64,
+}
+
+/// A histogram bucket covering totals in `start..=end`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub struct Bin {
+    pub start: u128,
+    pub end: u128,
+    pub count: usize,
+}
+
+/// Computes [`Stats`] over `calories`, bucketing them into at most `bins` equal-width bins.
+///
+/// Returns `None` if there are no elves.
+pub fn stats<T: Calories>(calories: &[T], bins: usize) -> Option<Stats> {
+    let mut sorted: Vec<u128> = calories.iter().map(|&c| c.into()).collect();
+    sorted.sort_unstable();
+    let (&min, &max) = (sorted.first()?, sorted.last()?);
+
+    let count = sorted.len();
+    let mean = sorted.iter().map(|&c| c as f64).sum::<f64>() / count as f64;
+    let variance = sorted
+        .iter()
+        .map(|&c| (c as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count as f64;
+
+    Some(Stats {
+        count,
+        min,
+        max,
+        mean,
+        median: percentile(&sorted, 50),
+        std_dev: variance.sqrt(),
+        percentiles: PERCENTILE_RANKS
+            .iter()
+            .map(|&rank| Percentile {
+                rank,
+                value: percentile(&sorted, rank),
+            })
+            .collect(),
+        histogram: histogram(&sorted, bins),
+    })
+}
+
+/// Linearly interpolated percentile of non-empty, sorted values.
+fn percentile(sorted: &[u128], rank: u8) -> f64 {
+    let position = f64::from(rank) / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    let fraction = position - lower as f64;
+    sorted[lower] as f64 + (sorted[upper] as f64 - sorted[lower] as f64) * fraction
+}
+
+/// Buckets non-empty, sorted values into at most `bins` equal-width bins spanning min..=max.
+fn histogram(sorted: &[u128], bins: usize) -> Vec<Bin> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let span = (max - min).saturating_add(1);
+    // Never make more bins than there are distinct values to put in them.
+    let bins = (bins.max(1) as u128).min(span);
+    let size = span.div_ceil(bins);
+
+    let mut histogram: Vec<Bin> = (0..bins)
+        .map(|i| {
+            let start = min.saturating_add(i.saturating_mul(size));
+            Bin {
+                start,
+                end: start.saturating_add(size - 1).min(max),
+                count: 0,
+            }
+        })
+        .collect();
+    for &value in sorted {
+        histogram[((value - min) / size) as usize].count += 1;
+    }
+    histogram
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves:   {}", self.count)?;
+        writeln!(f, "min:     {}", self.min)?;
+        writeln!(f, "max:     {}", self.max)?;
+        writeln!(f, "mean:    {:.2}", self.mean)?;
+        writeln!(f, "median:  {:.2}", self.median)?;
+        writeln!(f, "std dev: {:.2}", self.std_dev)?;
+        for p in &self.percentiles {
+            writeln!(f, "p{:<2}:     {:.2}", p.rank, p.value)?;
+        }
+
+        writeln!(f, "histogram:")?;
+        let label_width = self
+            .histogram
+            .iter()
+            .map(|bin| bin.end.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let tallest = self
+            .histogram
+            .iter()
+            .map(|bin| bin.count)
+            .max()
+            .unwrap_or(0);
+        for bin in &self.histogram {
+            // Scale bars to the tallest bin, but keep any non-empty bin visible.
+            let mut bar = bin.count * BAR_WIDTH / tallest.max(1);
+            if bin.count > 0 {
+                bar = bar.max(1);
+            }
+            writeln!(
+                f,
+                "  {:>label_width$}..={:<label_width$} | {} {}",
+                bin.start,
+                bin.end,
+                "#".repeat(bar),
+                bin.count
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use std::io::BufRead;
+
+use aoc_core::{Irregularity, ParseMode, Snippet};
+
+use crate::calories::{Calories, OverflowPolicy};
+use crate::elf::Elf;
+use crate::error::{EXPECTED_CALORIES, ParseError};
+
+/// Lazily reads each elf's block from any [`BufRead`], holding one line in memory at a time.
+///
+/// Yields `Ok(elf)` for every complete block and `Err` for every problem. A block with a
+/// problem is not yielded, and reading carries on after it, so callers can either stop at the
+/// first error or collect them all. Totals are accumulated in `T`.
+pub struct Elves<R, T = u64> {
+    reader: R,
+    // Reused for every line so reading doesn't allocate per line.
+    buffer: String,
+    line_no: usize,
+    policy: OverflowPolicy,
+    mode: ParseMode,
+    // Totals-only readers skip collecting items so each block takes constant memory.
+    keep_items: bool,
+    // Number of blocks finished so far, i.e. the index of the block being read.
+    blocks: usize,
+    in_block: bool,
+    // True before the first line and right after a blank one, where strict mode allows no
+    // further blank line.
+    at_boundary: bool,
+    // The elf currently being read, if any of its items have been added yet.
+    current: Option<Elf<T>>,
+    // Set once the current block has a problem: it won't be yielded, and an overflow in it
+    // isn't reported on top of the first problem.
+    poisoned: bool,
+    yielded_any: bool,
+    done: bool,
+}
+
+impl<R: BufRead> Elves<R> {
+    /// Wraps `reader`, which is only read as the iterator advances.
+    pub fn new(reader: R) -> Self {
+        Self::with_policy(reader, OverflowPolicy::Error)
+    }
+}
+
+impl<R: BufRead, T: Calories> Elves<R, T> {
+    /// Like [`Elves::new`], accumulating in `T` and handling overflow according to `policy`.
+    pub fn with_policy(reader: R, policy: OverflowPolicy) -> Self {
+        Self::build(reader, policy, true)
+    }
+
+    fn build(reader: R, policy: OverflowPolicy, keep_items: bool) -> Self {
+        Elves {
+            reader,
+            buffer: String::new(),
+            line_no: 0,
+            policy,
+            mode: ParseMode::Lenient,
+            keep_items,
+            blocks: 0,
+            in_block: false,
+            at_boundary: true,
+            current: None,
+            poisoned: false,
+            yielded_any: false,
+            done: false,
+        }
+    }
+
+    /// Parses in `mode` instead of the default [`ParseMode::Lenient`].
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    fn finish(&mut self) -> Option<Result<Elf<T>, ParseError>> {
+        self.done = true;
+        if let Some(elf) = self.current.take() {
+            return Some(Ok(elf));
+        }
+        (!self.yielded_any).then_some(Err(ParseError::Empty))
+    }
+
+    fn next_item(&mut self) -> Option<Result<Elf<T>, ParseError>> {
+        while !self.done {
+            self.buffer.clear();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return self.finish(),
+                Ok(_) => self.line_no += 1,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(ParseError::Io {
+                        line: self.line_no + 1,
+                        message: err.to_string(),
+                    }));
+                }
+            }
+
+            // Strip "\n" or "\r\n" in place, so Windows line endings cost no extra copy.
+            let line = match self.buffer.strip_suffix('\n') {
+                Some(line) => line.strip_suffix('\r').unwrap_or(line),
+                None => &self.buffer,
+            };
+            let line_no = self.line_no;
+
+            // An empty line closes the current block; runs of them don't create empty elves.
+            if line.is_empty() {
+                if self.mode == ParseMode::Strict && self.at_boundary {
+                    return Some(Err(ParseError::Irregular {
+                        line: line_no,
+                        irregularity: Irregularity::ExtraBlankLine,
+                        snippet: Snippet::new(line_no, line, 0, 0),
+                    }));
+                }
+                self.at_boundary = true;
+                if self.in_block {
+                    self.blocks += 1;
+                    self.in_block = false;
+                }
+                self.poisoned = false;
+                match self.current.take() {
+                    Some(elf) => return Some(Ok(elf)),
+                    None => continue,
+                }
+            }
+
+            self.at_boundary = false;
+            let strict = self.mode.check_spacing(line).or_else(|| {
+                (self.mode == ParseMode::Strict && line.starts_with('+')).then_some((
+                    Irregularity::LeadingPlus,
+                    0,
+                    1,
+                ))
+            });
+            if let Some((irregularity, start, len)) = strict {
+                self.current = None;
+                self.poisoned = true;
+                return Some(Err(ParseError::Irregular {
+                    line: line_no,
+                    irregularity,
+                    snippet: Snippet::new(line_no, line, start, len),
+                }));
+            }
+
+            // Ignore whitespace-only lines (defensive; blocks *shouldn't* contain these).
+            let token = line.trim();
+            if token.is_empty() {
+                continue;
+            }
+            self.in_block = true;
+
+            let start = line.len() - line.trim_start().len();
+            let snippet = || Snippet::new(line_no, line, start, token.len());
+
+            let Ok(n) = token.parse::<u64>() else {
+                self.current = None;
+                self.poisoned = true;
+                return Some(Err(ParseError::InvalidNumber {
+                    line: line_no,
+                    token: token.to_string(),
+                    expected: EXPECTED_CALORIES,
+                    snippet: snippet(),
+                }));
+            };
+            if self.poisoned {
+                continue;
+            }
+
+            let elf = self.current.get_or_insert_with(|| Elf {
+                index: self.blocks,
+                items: Vec::new(),
+                total: T::default(),
+            });
+            // Add to the elf's total; an item too big for `T` overflows just like a sum would.
+            let total = T::from_item(n).and_then(|item| elf.total.checked_add(item));
+            match (total, self.policy) {
+                (Some(total), _) => elf.total = total,
+                (None, OverflowPolicy::Saturate) => elf.total = T::MAX,
+                (None, OverflowPolicy::Error | OverflowPolicy::Promote) => {
+                    self.current = None;
+                    self.poisoned = true;
+                    return Some(Err(ParseError::Overflow {
+                        line: line_no,
+                        token: token.to_string(),
+                        width: T::NAME,
+                        snippet: snippet(),
+                    }));
+                }
+            }
+            if self.keep_items {
+                elf.items.push(n);
+            }
+        }
+        None
+    }
+}
+
+impl<R: BufRead, T: Calories> Iterator for Elves<R, T> {
+    type Item = Result<Elf<T>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.next_item();
+        self.yielded_any |= item.is_some();
+        item
+    }
+}
+
+/// Lazily sums each elf's block into a total of type `T`; the totals-only view of [`Elves`].
+///
+/// Items aren't kept, so each block takes constant memory however many lines it has.
+pub struct ElfTotals<R, T = u32>(Elves<R, T>);
+
+impl<R: BufRead> ElfTotals<R> {
+    /// Wraps `reader`, which is only read as the iterator advances.
+    pub fn new(reader: R) -> Self {
+        Self::with_policy(reader, OverflowPolicy::Error)
+    }
+}
+
+impl<R: BufRead, T: Calories> ElfTotals<R, T> {
+    /// Like [`ElfTotals::new`], accumulating in `T` and handling overflow according to `policy`.
+    pub fn with_policy(reader: R, policy: OverflowPolicy) -> Self {
+        ElfTotals(Elves::build(reader, policy, false))
+    }
+
+    /// Parses in `mode` instead of the default [`ParseMode::Lenient`].
+    pub fn mode(self, mode: ParseMode) -> Self {
+        ElfTotals(self.0.mode(mode))
+    }
+}
+
+impl<R: BufRead, T: Calories> Iterator for ElfTotals<R, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.0.next()?.map(|elf| elf.total))
+    }
+}
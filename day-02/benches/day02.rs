@@ -0,0 +1,62 @@
+use std::fs;
+use std::hint::black_box;
+
+use aoc_core::{ParseMode, Solution};
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use day_02::{Day02, Encoding, parse_strategy_guide, part_one, part_two};
+use rand::rngs::ChaCha8Rng;
+use rand::{RngExt, SeedableRng};
+
+/// A strategy guide of `rounds` uniformly random lines, the same on every run.
+fn generated(rounds: usize) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(2022);
+    let mut input = String::with_capacity(rounds * 4);
+    for _ in 0..rounds {
+        input.push(['A', 'B', 'C'][rng.random_range(0..3)]);
+        input.push(' ');
+        input.push(['X', 'Y', 'Z'][rng.random_range(0..3)]);
+        input.push('\n');
+    }
+    input
+}
+
+/// The real puzzle input if it's there, then a large generated guide.
+fn inputs() -> Vec<(&'static str, String)> {
+    let mut inputs = Vec::new();
+    if let Ok(real) = fs::read_to_string(Day02::DEFAULT_INPUT) {
+        inputs.push(("real", real));
+    }
+    inputs.push(("generated", generated(100_000)));
+    inputs
+}
+
+fn bench(c: &mut Criterion) {
+    let encoding = Encoding::default();
+    for (name, input) in inputs() {
+        let mut group = c.benchmark_group(format!("day02/{name}"));
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        let parse = |input: &str| parse_strategy_guide(input, &encoding, ParseMode::Lenient);
+        group.bench_function("parse_strategy_guide", |b| {
+            b.iter(|| parse(black_box(&input)))
+        });
+        let rounds = parse(&input).expect("benchmark input parses");
+        group.bench_function("part_one", |b| {
+            b.iter(|| part_one(black_box(&rounds), &encoding))
+        });
+        group.bench_function("part_two", |b| {
+            b.iter(|| part_two(black_box(&rounds), &encoding))
+        });
+        // Everything the binary does after reading the input.
+        group.bench_function("pipeline", |b| {
+            b.iter(|| {
+                let rounds = parse(black_box(&input)).expect("benchmark input parses");
+                (part_one(&rounds, &encoding), part_two(&rounds, &encoding))
+            })
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,227 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::error::{EncodingError, Field, TokenError};
+use crate::{MOVE_TOKENS, Move, OUTCOME_TOKENS, Outcome};
+
+/// Which tokens stand for which moves and outcomes in each column of a strategy guide.
+///
+/// The default is the puzzle's own: `A`/`X` is rock, `B`/`Y` paper and `C`/`Z` scissors in
+/// either column, and `X`/`Y`/`Z` read as an outcome are lose/draw/win. A config file only
+/// needs the columns it changes, e.g. in TOML:
+///
+/// ```toml
+/// [opponent]
+/// rock = "rock"
+/// paper = "paper"
+/// scissors = "scissors"
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Encoding {
+    /// Tokens in the first column, the opponent's move.
+    pub opponent: BTreeMap<String, Move>,
+    /// Tokens in the second column when it's read as my move (Part 1).
+    #[serde(rename = "move")]
+    pub my_move: BTreeMap<String, Move>,
+    /// Tokens in the second column when it's read as the desired outcome (Part 2).
+    pub outcome: BTreeMap<String, Outcome>,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        let moves: BTreeMap<String, Move> = MOVE_TOKENS
+            .iter()
+            .zip([Move::Rock, Move::Paper, Move::Scissors].iter().cycle())
+            .map(|(token, &m)| (token.to_string(), m))
+            .collect();
+        let outcomes = OUTCOME_TOKENS
+            .iter()
+            .zip([Outcome::Lose, Outcome::Draw, Outcome::Win])
+            .map(|(token, o)| (token.to_string(), o))
+            .collect();
+        Encoding {
+            opponent: moves.clone(),
+            my_move: moves,
+            outcome: outcomes,
+        }
+    }
+}
+
+/// Looks `token` up in one column, listing the column's tokens if it isn't there.
+fn lookup<T: Copy>(column: &BTreeMap<String, T>, token: &str) -> Result<T, TokenError> {
+    column.get(token).copied().ok_or_else(|| TokenError {
+        token: token.to_string(),
+        expected: column.keys().cloned().collect(),
+    })
+}
+
+/// Parses a column given on the command line as `TOKEN=VALUE` pairs separated by commas,
+/// e.g. `X=rock,Y=paper,Z=scissors`.
+pub fn parse_column<T: FromStr>(spec: &str) -> Result<BTreeMap<String, T>, EncodingError>
+where
+    T::Err: ToString,
+{
+    spec.split(',')
+        .map(|pair| {
+            let (token, value) = pair
+                .split_once('=')
+                .ok_or_else(|| EncodingError::BadPair(pair.to_string()))?;
+            let value = value
+                .trim()
+                .parse()
+                .map_err(|err: T::Err| EncodingError::BadValue {
+                    token: token.trim().to_string(),
+                    message: err.to_string(),
+                })?;
+            Ok((token.trim().to_string(), value))
+        })
+        .collect()
+}
+
+impl Encoding {
+    /// Reads the opponent's move from a first-column token.
+    pub fn opponent_move(&self, token: &str) -> Result<Move, TokenError> {
+        lookup(&self.opponent, token)
+    }
+
+    /// Reads my move from a second-column token.
+    pub fn my_move(&self, token: &str) -> Result<Move, TokenError> {
+        lookup(&self.my_move, token)
+    }
+
+    /// Reads the desired outcome from a second-column token.
+    pub fn outcome(&self, token: &str) -> Result<Outcome, TokenError> {
+        lookup(&self.outcome, token)
+    }
+
+    /// Tokens the second column accepts: those that read as a move, an outcome or both.
+    pub fn column2_tokens(&self) -> Vec<String> {
+        let tokens: BTreeSet<&String> = self.my_move.keys().chain(self.outcome.keys()).collect();
+        tokens.into_iter().cloned().collect()
+    }
+
+    /// Parses and validates an encoding written in TOML.
+    pub fn from_toml(contents: &str) -> Result<Self, EncodingError> {
+        let encoding: Encoding = toml::from_str(contents)?;
+        encoding.validate()?;
+        Ok(encoding)
+    }
+
+    /// Parses and validates an encoding written in JSON.
+    pub fn from_json(contents: &str) -> Result<Self, EncodingError> {
+        let encoding: Encoding = serde_json::from_str(contents)?;
+        encoding.validate()?;
+        Ok(encoding)
+    }
+
+    /// Loads an encoding from a file, as JSON if it ends in `.json` and as TOML otherwise.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let encoding = if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&contents)
+        } else {
+            Self::from_toml(&contents)
+        };
+        encoding.with_context(|| format!("Invalid encoding in {}", path.display()))
+    }
+
+    /// Checks that every column can actually match a token of a guide.
+    pub fn validate(&self) -> Result<(), EncodingError> {
+        let columns: [(Field, Vec<&String>); 3] = [
+            (Field::OpponentMove, self.opponent.keys().collect()),
+            (Field::MyMove, self.my_move.keys().collect()),
+            (Field::DesiredOutcome, self.outcome.keys().collect()),
+        ];
+        for (field, tokens) in columns {
+            if tokens.is_empty() {
+                return Err(EncodingError::EmptyColumn(field));
+            }
+            // Lines are split on whitespace, so such a token could never match.
+            if let Some(token) = tokens
+                .into_iter()
+                .find(|t| t.is_empty() || t.contains(char::is_whitespace))
+            {
+                return Err(EncodingError::UnmatchableToken {
+                    field,
+                    token: token.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day02, parse_strategy_guide, trace};
+    use aoc_core::{ParseMode, Part, Solution};
+
+    #[test]
+    fn custom_encodings_read_the_same_guide() -> anyhow::Result<()> {
+        let toml = r#"
+            [opponent]
+            "🪨" = "rock"
+            "📄" = "paper"
+            "✂" = "scissors"
+
+            [move]
+            rock = "rock"
+            paper = "paper"
+            scissors = "scissors"
+
+            [outcome]
+            rock = "lose"
+            paper = "draw"
+            scissors = "win"
+        "#;
+        let encoding = Encoding::from_toml(toml)?;
+        let guide = parse_strategy_guide(
+            "🪨 paper\n📄 rock\n✂ scissors\n",
+            &encoding,
+            ParseMode::Lenient,
+        )?;
+        // Tokens differ, so rounds are compared by how each part plays them.
+        let sample = Day02::parse("A Y\nB X\nC Z\n")?;
+        for part in Part::ALL {
+            assert_eq!(
+                trace(&guide, &encoding, part),
+                trace(&sample, &Encoding::default(), part)
+            );
+        }
+        assert_eq!(encoding.column2_tokens(), ["paper", "rock", "scissors"]);
+
+        // Columns left out keep the default tokens.
+        let encoding =
+            Encoding::from_json(r#"{"outcome": {"L": "lose", "D": "draw", "W": "win"}}"#)?;
+        assert_eq!(encoding.opponent, Encoding::default().opponent);
+        assert_eq!(encoding.outcome("W"), Ok(Outcome::Win));
+        // The second column accepts a token that reads only one way.
+        assert_eq!(
+            encoding.column2_tokens(),
+            ["A", "B", "C", "D", "L", "W", "X", "Y", "Z"]
+        );
+
+        let column = parse_column::<Outcome>("L=lose, D=draw,W=win")?;
+        assert_eq!(column, encoding.outcome);
+        assert!(matches!(
+            parse_column::<Move>("X=rock,Y"),
+            Err(EncodingError::BadPair(pair)) if pair == "Y"
+        ));
+        assert!(matches!(
+            Encoding::from_json(r#"{"move": {"two words": "rock"}}"#),
+            Err(EncodingError::UnmatchableToken {
+                field: Field::MyMove,
+                ..
+            })
+        ));
+        Ok(())
+    }
+}
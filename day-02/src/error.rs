@@ -0,0 +1,140 @@
+use std::fmt;
+
+use aoc_core::{Diagnostic, Irregularity, Snippet};
+use thiserror::Error;
+
+/// Which column of a strategy-guide line a token was read as.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Field {
+    OpponentMove,
+    MyMove,
+    DesiredOutcome,
+    /// The second column before it's read as either a move or an outcome.
+    SecondColumn,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Field::OpponentMove => "opponent move",
+            Field::MyMove => "my move",
+            Field::DesiredOutcome => "desired outcome",
+            Field::SecondColumn => "move or outcome",
+        })
+    }
+}
+
+/// A token that names no move or outcome, without any position information.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error("invalid token {token:?}, expected one of {}", .expected.join(", "))]
+pub struct TokenError {
+    pub token: String,
+    pub expected: Vec<String>,
+}
+
+/// Why a line of the strategy guide could not be parsed.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ParseError {
+    /// A token that doesn't belong to the set accepted for its column.
+    #[error("line {line}: invalid {field} {token:?}, expected one of {}\n{snippet}", .expected.join(", "))]
+    InvalidToken {
+        line: usize,
+        field: Field,
+        token: String,
+        expected: Vec<String>,
+        snippet: Snippet,
+    },
+    /// The line ended before the column was reached.
+    #[error("line {line}: missing {field}, expected one of {}\n{snippet}", .expected.join(", "))]
+    MissingToken {
+        line: usize,
+        field: Field,
+        expected: Vec<String>,
+        snippet: Snippet,
+    },
+    /// Layout that [`ParseMode::Strict`](aoc_core::ParseMode::Strict) rejects.
+    #[error("line {line}: {irregularity} is not allowed in strict mode\n{snippet}")]
+    Irregular {
+        line: usize,
+        irregularity: Irregularity,
+        snippet: Snippet,
+    },
+}
+
+impl ParseError {
+    /// The 1-based line the error points at.
+    pub fn line(&self) -> usize {
+        match self {
+            ParseError::InvalidToken { line, .. }
+            | ParseError::MissingToken { line, .. }
+            | ParseError::Irregular { line, .. } => *line,
+        }
+    }
+}
+
+impl Diagnostic for ParseError {
+    fn kind(&self) -> &'static str {
+        match self {
+            ParseError::InvalidToken { .. } => "invalid token",
+            ParseError::MissingToken { .. } => "missing token",
+            ParseError::Irregular { .. } => "irregular line",
+        }
+    }
+}
+
+/// Why a list of shapes doesn't make a valid cyclic [`Game`](crate::Game).
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum GameError {
+    #[error("a cyclic game needs an odd number of shapes, at least 3, but got {0}")]
+    InvalidSize(usize),
+    #[error("shape {name:?} is listed again at position {index}")]
+    DuplicateShape { name: String, index: usize },
+}
+
+/// Why a token encoding couldn't be loaded.
+#[derive(Debug, Error)]
+pub enum EncodingError {
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("{0:?} is not of the form TOKEN=VALUE")]
+    BadPair(String),
+    #[error("token {token:?}: {message}")]
+    BadValue { token: String, message: String },
+    #[error("no tokens are given for the {0} column")]
+    EmptyColumn(Field),
+    #[error("{field} token {token:?} is empty or contains whitespace, so it can never match")]
+    UnmatchableToken { field: Field, token: String },
+}
+
+/// Why a [`StrategySpec`](crate::StrategySpec) couldn't be built.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum StrategyError {
+    /// A round of the guide has no reading under the part the strategy follows.
+    #[error(transparent)]
+    Guide(#[from] ParseError),
+    #[error("strategy {0:?} has no moves to play")]
+    NoMoves(String),
+}
+
+/// Why a guide couldn't be scored under a set of [`ScoringRules`](crate::ScoringRules).
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ScoringError {
+    /// A round of the guide has no reading under the part being scored.
+    #[error(transparent)]
+    Guide(#[from] ParseError),
+    #[error("the part {0} total doesn't fit in 64 bits")]
+    Overflow(u8),
+}
+
+/// Why `--explore` can't read a guide's second column: it uses more than three tokens.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error(
+    "the second column uses {} tokens ({}), but only three can be explored",
+    .tokens.len(),
+    .tokens.join(", ")
+)]
+pub struct ExploreError {
+    pub tokens: Vec<String>,
+}
@@ -0,0 +1,247 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::error::ExploreError;
+use crate::{Encoding, Move, Outcome, Round, outcome, required_move, round_score};
+
+/// Every ordering of three things, as indices; the first is the identity.
+const PERMUTATIONS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+const MOVES: [Move; 3] = Move::ALL;
+const OUTCOMES: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+/// One way to read the second column: what each of its three tokens means, in the order of
+/// [`Exploration::tokens`] (`X`, `Y`, `Z` by default).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Interpretation {
+    /// The tokens are the move I play.
+    Moves([Move; 3]),
+    /// The tokens are the outcome I need.
+    Outcomes([Outcome; 3]),
+}
+
+impl Interpretation {
+    /// All 12 interpretations: the 6 bijections onto moves, then the 6 onto outcomes.
+    pub fn all() -> impl Iterator<Item = Interpretation> {
+        let moves = PERMUTATIONS.map(|p| Interpretation::Moves(p.map(|i| MOVES[i])));
+        let outcomes = PERMUTATIONS.map(|p| Interpretation::Outcomes(p.map(|i| OUTCOMES[i])));
+        moves.into_iter().chain(outcomes)
+    }
+
+    /// The part whose reading under `encoding` this is, if any, with the second column's
+    /// tokens listed as in [`Exploration::tokens`].
+    pub fn puzzle_part(self, encoding: &Encoding, tokens: &[String; 3]) -> Option<u8> {
+        match self {
+            Interpretation::Moves(moves) => tokens
+                .iter()
+                .zip(moves)
+                .all(|(token, m)| encoding.my_move(token) == Ok(m))
+                .then_some(1),
+            Interpretation::Outcomes(outcomes) => tokens
+                .iter()
+                .zip(outcomes)
+                .all(|(token, o)| encoding.outcome(token) == Ok(o))
+                .then_some(2),
+        }
+    }
+
+    /// The move I play against `opponent` when the second column holds the token at `index`
+    /// of [`Exploration::tokens`].
+    pub fn my_move(self, opponent: Move, index: usize) -> Move {
+        match self {
+            Interpretation::Moves(moves) => moves[index],
+            Interpretation::Outcomes(outcomes) => required_move(opponent, outcomes[index]),
+        }
+    }
+}
+
+/// An interpretation's result over a whole guide.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Scored {
+    pub interpretation: Interpretation,
+    /// See [`Interpretation::puzzle_part`].
+    pub part: Option<u8>,
+    pub score: u32,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+/// Every interpretation of a guide's second column, best score first.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Exploration {
+    /// The second column's tokens, in the order interpretations list their meanings.
+    pub tokens: [String; 3],
+    pub ranked: Vec<Scored>,
+}
+
+/// Scores `rounds` under every [`Interpretation`] of the second-column tokens they use,
+/// marking the ones `encoding` reads Parts 1 and 2 with.
+///
+/// A guide using fewer than three tokens is explored as if it used others `encoding` accepts
+/// too. Ties keep the order of [`Interpretation::all`].
+pub fn explore(rounds: &[Round], encoding: &Encoding) -> Result<Exploration, ExploreError> {
+    let used: BTreeSet<&String> = rounds.iter().map(|round| &round.column2).collect();
+    if used.len() > 3 {
+        return Err(ExploreError {
+            tokens: used.into_iter().cloned().collect(),
+        });
+    }
+    // Pad with unused tokens, preferring those that read both ways like the puzzle's own.
+    let mut unused: Vec<String> = (encoding.column2_tokens().into_iter())
+        .filter(|token| !used.contains(token))
+        .collect();
+    unused
+        .sort_by_key(|token| encoding.my_move(token).is_err() || encoding.outcome(token).is_err());
+    let mut columns: Vec<String> = used.into_iter().cloned().collect();
+    columns.extend(unused.into_iter().take(3 - columns.len()));
+    columns.sort_unstable();
+    let tokens: [String; 3] =
+        std::array::from_fn(|i| columns.get(i).cloned().unwrap_or_else(|| "?".to_string()));
+    // Each round's token as an index into `tokens`, which holds every token used.
+    let indices: Vec<usize> = rounds
+        .iter()
+        .map(|round| {
+            (tokens.iter())
+                .position(|token| *token == round.column2)
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut ranked: Vec<Scored> = Interpretation::all()
+        .map(|interpretation| {
+            let mut scored = Scored {
+                interpretation,
+                part: interpretation.puzzle_part(encoding, &tokens),
+                score: 0,
+                wins: 0,
+                draws: 0,
+                losses: 0,
+            };
+            for (round, &index) in rounds.iter().zip(&indices) {
+                let me = interpretation.my_move(round.opponent, index);
+                scored.score += round_score(round.opponent, me);
+                match outcome(round.opponent, me) {
+                    Outcome::Win => scored.wins += 1,
+                    Outcome::Draw => scored.draws += 1,
+                    Outcome::Lose => scored.losses += 1,
+                }
+            }
+            scored
+        })
+        .collect();
+    ranked.sort_by_key(|scored| std::cmp::Reverse(scored.score));
+    Ok(Exploration { tokens, ranked })
+}
+
+impl fmt::Display for Exploration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c] = &self.tokens;
+        writeln!(
+            f,
+            "rank  reading   {a:<9} {b:<9} {c:<9} {:>8}  win/draw/loss",
+            "score"
+        )?;
+        for (rank, scored) in self.ranked.iter().enumerate() {
+            let (reading, meanings) = match scored.interpretation {
+                Interpretation::Moves(moves) => ("move", moves.map(|m| m.to_string())),
+                Interpretation::Outcomes(outcomes) => ("outcome", outcomes.map(|o| o.to_string())),
+            };
+            let [a, b, c] = meanings;
+            write!(
+                f,
+                "{:>4}  {reading:<9} {a:<9} {b:<9} {c:<9} {:>8}  {}/{}/{}",
+                rank + 1,
+                scored.score,
+                scored.wins,
+                scored.draws,
+                scored.losses
+            )?;
+            if let Some(part) = scored.part {
+                write!(f, "  (part {part})")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::parse_column;
+    use crate::{Day02, parse_strategy_guide};
+    use aoc_core::{ParseMode, Solution};
+
+    #[test]
+    fn explorer_ranks_every_reading_including_both_parts() -> anyhow::Result<()> {
+        let guide = Day02::parse("A Y\nB X\nC Z\n")?;
+        let exploration = explore(&guide, &Encoding::default())?;
+
+        assert_eq!(exploration.tokens, ["X", "Y", "Z"]);
+        assert_eq!(exploration.ranked.len(), 12);
+        assert!(
+            exploration
+                .ranked
+                .windows(2)
+                .all(|pair| pair[0].score >= pair[1].score)
+        );
+        let part = |n| {
+            exploration
+                .ranked
+                .iter()
+                .find(|s| s.part == Some(n))
+                .unwrap()
+        };
+        assert_eq!(part(1).score, Day02::part_one(&guide)?);
+        assert_eq!(part(2).score, Day02::part_two(&guide)?);
+        assert_eq!((part(1).wins, part(1).draws, part(1).losses), (1, 1, 1));
+
+        // The sample's opponents all play differently, so some reading wins every round.
+        let best = exploration.ranked[0];
+        assert_eq!((best.score, best.wins), (24, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn explorer_labels_parts_by_the_encoding() -> anyhow::Result<()> {
+        // Tokens are told apart by themselves, not by what Part 2 reads them as.
+        let mut encoding = Encoding {
+            outcome: parse_column("X=win,Y=draw,Z=lose")?,
+            ..Encoding::default()
+        };
+        let guide = parse_strategy_guide("A Y\nB X\n", &encoding, ParseMode::Lenient)?;
+        let exploration = explore(&guide, &encoding)?;
+        let part = |n| {
+            exploration
+                .ranked
+                .iter()
+                .find(|s| s.part == Some(n))
+                .unwrap()
+        };
+        assert_eq!(part(1).score, 9);
+        assert_eq!(
+            part(2).interpretation,
+            Interpretation::Outcomes([Outcome::Win, Outcome::Draw, Outcome::Lose])
+        );
+
+        // Two tokens with the same outcome stay apart, and only the ones used are explored.
+        encoding.outcome = parse_column("X=lose,Y=draw,Z=win,A=win")?;
+        let guide = parse_strategy_guide("A Z\nB A\nC Y\n", &encoding, ParseMode::Lenient)?;
+        let exploration = explore(&guide, &encoding)?;
+        assert_eq!(exploration.tokens, ["A", "Y", "Z"]);
+        assert!(exploration.ranked.iter().any(|s| s.part == Some(1)));
+        assert!(exploration.ranked.iter().all(|s| s.part != Some(2)));
+
+        let guide = parse_strategy_guide("A Z\nB A\nC Y\nA X\n", &encoding, ParseMode::Lenient)?;
+        assert!(explore(&guide, &encoding).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use crate::Outcome;
+use crate::error::GameError;
+
+/// A shape in a [`Game`], identified by its position in the game's cycle.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Shape(pub(crate) usize);
+
+impl Shape {
+    /// 0-based position of the shape in its game's cycle.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// An odd-sized cyclic game in the family of rock-paper-scissors.
+///
+/// Shapes are listed so that each one beats the `(n - 1) / 2` shapes just before it, wrapping
+/// around, and loses to the ones just after it. Everything else is derived from that cycle:
+/// a shape scores its 1-based position, and a round scores `0`, `n` or `2n` for a loss, draw
+/// or win, which for three shapes is the puzzle's 1/2/3 and 0/3/6.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Game<'a> {
+    names: &'a [&'a str],
+}
+
+impl<'a> Game<'a> {
+    /// Classic rock-paper-scissors, the game played in the puzzle.
+    pub const ROCK_PAPER_SCISSORS: Game<'static> = Game {
+        names: &["rock", "paper", "scissors"],
+    };
+    /// Rock-paper-scissors-lizard-Spock.
+    pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: Game<'static> = Game {
+        names: &["rock", "spock", "paper", "lizard", "scissors"],
+    };
+    /// RPS-7, where every shape beats three others.
+    pub const RPS_7: Game<'static> = Game {
+        names: &[
+            "rock", "water", "air", "paper", "sponge", "scissors", "fire",
+        ],
+    };
+
+    /// A game over `names`, listed in cycle order; there must be an odd number, at least 3.
+    pub fn new(names: &'a [&'a str]) -> Result<Self, GameError> {
+        if names.len() < 3 || names.len().is_multiple_of(2) {
+            return Err(GameError::InvalidSize(names.len()));
+        }
+        if let Some((i, name)) = names
+            .iter()
+            .enumerate()
+            .find(|(i, name)| names[..*i].contains(name))
+        {
+            return Err(GameError::DuplicateShape {
+                name: name.to_string(),
+                index: i,
+            });
+        }
+        Ok(Game { names })
+    }
+
+    /// Number of shapes in the cycle.
+    pub fn size(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Every shape, in cycle order.
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> + use<> {
+        (0..self.size()).map(Shape)
+    }
+
+    /// The shape at `index` in the cycle, if there is one.
+    pub fn shape(&self, index: usize) -> Option<Shape> {
+        (index < self.size()).then_some(Shape(index))
+    }
+
+    /// Looks a shape up by name.
+    pub fn shape_named(&self, name: &str) -> Option<Shape> {
+        self.names.iter().position(|&n| n == name).map(Shape)
+    }
+
+    /// The name `shape` was given in the cycle.
+    pub fn name(&self, shape: Shape) -> &'a str {
+        self.names[shape.0]
+    }
+
+    /// How a round ends for `me` against `opponent`.
+    pub fn outcome(&self, opponent: Shape, me: Shape) -> Outcome {
+        // How far `me` is ahead of `opponent` around the cycle.
+        let ahead = (me.0 + self.size() - opponent.0) % self.size();
+        match ahead {
+            0 => Outcome::Draw,
+            d if d <= self.size() / 2 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+
+    /// Points awarded for playing `shape`.
+    pub fn shape_score(&self, shape: Shape) -> u32 {
+        shape.0 as u32 + 1
+    }
+
+    /// Points awarded for ending a round with `outcome`.
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        let size = self.size() as u32;
+        match outcome {
+            Outcome::Lose => 0,
+            Outcome::Draw => size,
+            Outcome::Win => 2 * size,
+        }
+    }
+
+    /// Total score for a single round: outcome points plus the points for my shape.
+    pub fn round_score(&self, opponent: Shape, me: Shape) -> u32 {
+        self.outcome_score(self.outcome(opponent, me)) + self.shape_score(me)
+    }
+
+    /// A shape I can play against `opponent` to end the round with `desired`.
+    ///
+    /// When several shapes would do, this picks the one next to `opponent` in the cycle.
+    pub fn required_move(&self, opponent: Shape, desired: Outcome) -> Shape {
+        let offset = match desired {
+            Outcome::Draw => 0,
+            Outcome::Win => 1,
+            Outcome::Lose => self.size() - 1,
+        };
+        Shape((opponent.0 + offset) % self.size())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn larger_games_follow_their_rules() {
+        let game = Game::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+        let shape = |name| game.shape_named(name).unwrap();
+        let wins = [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "spock"),
+            ("spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "spock"),
+            ("spock", "rock"),
+            ("rock", "scissors"),
+        ];
+        for (winner, loser) in wins {
+            assert_eq!(game.outcome(shape(loser), shape(winner)), Outcome::Win);
+            assert_eq!(game.outcome(shape(winner), shape(loser)), Outcome::Lose);
+        }
+
+        for game in [Game::ROCK_PAPER_SCISSORS_LIZARD_SPOCK, Game::RPS_7] {
+            for opponent in game.shapes() {
+                let wins = game
+                    .shapes()
+                    .filter(|&me| game.outcome(opponent, me) == Outcome::Win)
+                    .count();
+                assert_eq!(wins, game.size() / 2);
+                for desired in [Outcome::Lose, Outcome::Draw, Outcome::Win] {
+                    let me = game.required_move(opponent, desired);
+                    assert_eq!(game.outcome(opponent, me), desired);
+                }
+            }
+        }
+
+        assert_eq!(Game::new(&["a", "b"]), Err(GameError::InvalidSize(2)));
+        assert_eq!(
+            Game::new(&["a", "b", "a"]),
+            Err(GameError::DuplicateShape {
+                name: "a".to_string(),
+                index: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,638 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_core::{Diagnostics, Irregularity, ParseMode, Part, Snippet, Solution};
+use serde::{Deserialize, Serialize};
+
+pub mod encoding;
+pub mod error;
+pub mod explore;
+pub mod game;
+pub mod optimal;
+pub mod scoring;
+pub mod simulate;
+pub mod trace;
+
+pub use encoding::Encoding;
+pub use error::{
+    EncodingError, ExploreError, Field, GameError, ParseError, ScoringError, StrategyError,
+    TokenError,
+};
+pub use explore::{Exploration, Interpretation, explore};
+pub use game::{Game, Shape};
+pub use optimal::{Bounds, Constraints, Goal, Plan, bounds};
+pub use scoring::{Comparison, RuleSet, ScoringRules, compare};
+pub use simulate::{Strategy, StrategySpec, Tournament};
+pub use trace::{TraceFormat, TracedRound, trace};
+
+/// Tokens accepted as a move in either column by the default [`Encoding`].
+pub const MOVE_TOKENS: [&str; 6] = ["A", "B", "C", "X", "Y", "Z"];
+/// Tokens accepted as a desired outcome by the default [`Encoding`].
+pub const OUTCOME_TOKENS: [&str; 3] = ["X", "Y", "Z"];
+
+/// A shape played in a round of rock-paper-scissors.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+/// The result of a round from my point of view.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Move::Rock => "rock",
+            Move::Paper => "paper",
+            Move::Scissors => "scissors",
+        })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Outcome::Lose => "lose",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        })
+    }
+}
+
+fn token_error(token: &str, expected: &[&str]) -> TokenError {
+    TokenError {
+        token: token.to_string(),
+        expected: expected.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+impl FromStr for Move {
+    type Err = TokenError;
+
+    /// Parses a move by name, as used in an [`Encoding`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rock" => Ok(Move::Rock),
+            "paper" => Ok(Move::Paper),
+            "scissors" => Ok(Move::Scissors),
+            other => Err(token_error(other, &["rock", "paper", "scissors"])),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = TokenError;
+
+    /// Parses an outcome by name, as used in an [`Encoding`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lose" => Ok(Outcome::Lose),
+            "draw" => Ok(Outcome::Draw),
+            "win" => Ok(Outcome::Win),
+            other => Err(token_error(other, &["lose", "draw", "win"])),
+        }
+    }
+}
+
+impl Move {
+    /// Every move, in the order of [`Game::ROCK_PAPER_SCISSORS`].
+    pub const ALL: [Move; 3] = [Move::Rock, Move::Paper, Move::Scissors];
+
+    /// This move's shape in [`Game::ROCK_PAPER_SCISSORS`].
+    pub fn shape(self) -> Shape {
+        Shape(match self {
+            Move::Rock => 0,
+            Move::Paper => 1,
+            Move::Scissors => 2,
+        })
+    }
+
+    /// The move for a shape of [`Game::ROCK_PAPER_SCISSORS`].
+    pub fn from_shape(shape: Shape) -> Move {
+        match shape.index() {
+            0 => Move::Rock,
+            1 => Move::Paper,
+            _ => Move::Scissors,
+        }
+    }
+
+    /// Points awarded for playing this shape.
+    pub fn shape_score(self) -> u32 {
+        Game::ROCK_PAPER_SCISSORS.shape_score(self.shape())
+    }
+}
+
+/// How a round ends for me when I play `me` against `opponent`.
+pub fn outcome(opponent: Move, me: Move) -> Outcome {
+    Game::ROCK_PAPER_SCISSORS.outcome(opponent.shape(), me.shape())
+}
+
+/// Total score for a single round: outcome points plus the points for my shape.
+pub fn round_score(opponent: Move, me: Move) -> u32 {
+    Game::ROCK_PAPER_SCISSORS.round_score(opponent.shape(), me.shape())
+}
+
+/// The shape I have to play against `opponent` to end the round with `desired`.
+pub fn required_move(opponent: Move, desired: Outcome) -> Move {
+    Move::from_shape(Game::ROCK_PAPER_SCISSORS.required_move(opponent.shape(), desired))
+}
+
+/// One line of the strategy guide, parsed once; each part is an interpretation of it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Round {
+    /// 1-based line of the guide the round was read from.
+    pub line: usize,
+    pub opponent: Move,
+    /// The second column's token as written; each part reads it through the [`Encoding`].
+    pub column2: String,
+    /// Marks `column2` in its line, for a part that can't read it to point at.
+    pub snippet: Snippet,
+}
+
+impl Round {
+    /// The move I play in this round under `part`'s reading of the second column, or why
+    /// `encoding` has no such reading of its token.
+    ///
+    /// A token may read only one way, so only the part that needs the other runs into it.
+    pub fn my_move(&self, part: Part, encoding: &Encoding) -> Result<Move, ParseError> {
+        let invalid = |field, err| invalid_token_at(field, self.snippet.clone(), err);
+        match part {
+            Part::One => {
+                (encoding.my_move(&self.column2)).map_err(|err| invalid(Field::MyMove, err))
+            }
+            Part::Two => encoding
+                .outcome(&self.column2)
+                .map(|desired| required_move(self.opponent, desired))
+                .map_err(|err| invalid(Field::DesiredOutcome, err)),
+        }
+    }
+
+    /// This round's score under `part`'s reading of the second column.
+    pub fn score(&self, part: Part, encoding: &Encoding) -> Result<u32, ParseError> {
+        Ok(round_score(self.opponent, self.my_move(part, encoding)?))
+    }
+}
+
+/// Splits a line into whitespace-separated tokens along with their byte offsets.
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace()
+        .map(move |token| (token.as_ptr() as usize - line.as_ptr() as usize, token))
+}
+
+/// `err` as a problem with the token `snippet` marks, read as `field`.
+fn invalid_token_at(field: Field, snippet: Snippet, err: TokenError) -> ParseError {
+    ParseError::InvalidToken {
+        line: snippet.line,
+        field,
+        token: err.token,
+        expected: err.expected,
+        snippet,
+    }
+}
+
+/// `err` as a problem with the token at byte `start` of line `line_no`, read as `field`.
+fn invalid_token(
+    line_no: usize,
+    line: &str,
+    field: Field,
+    start: usize,
+    err: TokenError,
+) -> ParseError {
+    let snippet = Snippet::new(line_no, line, start, err.token.len());
+    invalid_token_at(field, snippet, err)
+}
+
+/// The next token of line `line_no`, or an error pointing past its end if there's none.
+fn next_token<'a>(
+    tokens: &mut impl Iterator<Item = (usize, &'a str)>,
+    line_no: usize,
+    line: &str,
+    field: Field,
+    expected: impl FnOnce() -> Vec<String>,
+) -> Result<(usize, &'a str), ParseError> {
+    tokens.next().ok_or_else(|| ParseError::MissingToken {
+        line: line_no,
+        field,
+        expected: expected(),
+        snippet: Snippet::new(line_no, line, line.len(), 0),
+    })
+}
+
+/// Parses both columns of line `line_no`, returning the problem with each one that has one.
+///
+/// The second column is only an error if it reads neither way; otherwise it's its token and
+/// where that sits in the line.
+fn parse_columns(
+    line_no: usize,
+    line: &str,
+    encoding: &Encoding,
+) -> (
+    Result<Move, ParseError>,
+    Result<(String, Snippet), ParseError>,
+) {
+    let mut parts = tokens(line);
+    let opponent = next_token(&mut parts, line_no, line, Field::OpponentMove, || {
+        encoding.opponent.keys().cloned().collect()
+    })
+    .and_then(|(start, token)| {
+        (encoding.opponent_move(token))
+            .map_err(|err| invalid_token(line_no, line, Field::OpponentMove, start, err))
+    });
+    let column2 = next_token(&mut parts, line_no, line, Field::SecondColumn, || {
+        encoding.column2_tokens()
+    })
+    .and_then(|(start, token)| {
+        if encoding.my_move(token).is_err() && encoding.outcome(token).is_err() {
+            let err = TokenError {
+                token: token.to_string(),
+                expected: encoding.column2_tokens(),
+            };
+            return Err(invalid_token(
+                line_no,
+                line,
+                Field::SecondColumn,
+                start,
+                err,
+            ));
+        }
+        let snippet = Snippet::new(line_no, line, start, token.len());
+        Ok((token.to_string(), snippet))
+    });
+    (opponent, column2)
+}
+
+/// In strict mode, the first blank line, tab, stray space or third token on line `line_no`.
+fn check_layout(line_no: usize, line: &str, mode: ParseMode) -> Option<ParseError> {
+    if mode == ParseMode::Lenient {
+        return None;
+    }
+    let (irregularity, start, len) = if line.is_empty() {
+        (Irregularity::ExtraBlankLine, 0, 0)
+    } else if let Some(found) = mode.check_spacing(line) {
+        found
+    } else {
+        let (start, token) = tokens(line).nth(2)?;
+        (Irregularity::TrailingToken, start, token.len())
+    };
+    Some(ParseError::Irregular {
+        line: line_no,
+        irregularity,
+        snippet: Snippet::new(line_no, line, start, len),
+    })
+}
+
+/// Parses line `line_no` as a [`Round`] in `mode`, stopping at its first problem.
+pub fn parse_round(
+    line_no: usize,
+    line: &str,
+    encoding: &Encoding,
+    mode: ParseMode,
+) -> Result<Round, ParseError> {
+    if let Some(err) = check_layout(line_no, line, mode) {
+        return Err(err);
+    }
+    let (opponent, column2) = parse_columns(line_no, line, encoding);
+    let (column2, snippet) = column2?;
+    Ok(Round {
+        line: line_no,
+        opponent: opponent?,
+        column2,
+        snippet,
+    })
+}
+
+/// Numbers the lines of `contents` from 1, so errors point at the real line.
+///
+/// Lenient mode drops blank lines here; strict mode keeps them so they can be reported.
+fn guide_lines(contents: &str, mode: ParseMode) -> impl Iterator<Item = (usize, &str)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(move |(_, line)| mode == ParseMode::Strict || !line.trim().is_empty())
+}
+
+/// Validates the whole guide in `mode`, collecting every invalid and missing token.
+///
+/// Unlike [`parse_strategy_guide`], a bad opponent move doesn't hide a problem in the second
+/// column, and neither hides a problem with the line's layout.
+pub fn diagnose_strategy_guide(
+    contents: &str,
+    encoding: &Encoding,
+    mode: ParseMode,
+) -> Diagnostics<ParseError> {
+    let errors = guide_lines(contents, mode)
+        .flat_map(|(line_no, line)| {
+            let layout = check_layout(line_no, line, mode);
+            // A blank line has nothing to report beyond being there.
+            let columns = (!line.trim().is_empty()).then(|| parse_columns(line_no, line, encoding));
+            let (opponent, column2) = columns.map_or((None, None), |(o, c)| {
+                // A token that reads one way is still reported, as a guide only one part can
+                // read, but only once.
+                let column2 = match c {
+                    Ok((token, snippet)) => {
+                        let unread = match (encoding.my_move(&token), encoding.outcome(&token)) {
+                            (Err(err), _) => Some((Field::MyMove, err)),
+                            (_, Err(err)) => Some((Field::DesiredOutcome, err)),
+                            _ => None,
+                        };
+                        unread.map(|(field, err)| invalid_token_at(field, snippet, err))
+                    }
+                    Err(err) => Some(err),
+                };
+                (o.err(), column2)
+            });
+            [layout, opponent, column2].into_iter().flatten()
+        })
+        .collect();
+    Diagnostics { errors }
+}
+
+/// Parses every line of the strategy guide in `mode`, in a single pass.
+///
+/// Lenient mode skips blank lines; strict mode rejects them.
+pub fn parse_strategy_guide(
+    contents: &str,
+    encoding: &Encoding,
+    mode: ParseMode,
+) -> Result<Vec<Round>, ParseError> {
+    guide_lines(contents, mode)
+        .map(|(line_no, line)| parse_round(line_no, line, encoding, mode))
+        .collect()
+}
+
+/// Part 1: total score when `encoding` reads the second column as the move I play.
+pub fn part_one(rounds: &[Round], encoding: &Encoding) -> Result<u32, ParseError> {
+    rounds
+        .iter()
+        .map(|round| round.score(Part::One, encoding))
+        .sum()
+}
+
+/// Part 2: total score when `encoding` reads the second column as the outcome I need.
+pub fn part_two(rounds: &[Round], encoding: &Encoding) -> Result<u32, ParseError> {
+    rounds
+        .iter()
+        .map(|round| round.score(Part::Two, encoding))
+        .sum()
+}
+
+/// Day 2: Rock Paper Scissors.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const DEFAULT_INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+    const EXAMPLE_INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt");
+
+    type Input = Vec<Round>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse_with(input: &str, mode: ParseMode) -> anyhow::Result<Self::Input> {
+        Ok(parse_strategy_guide(input, &Encoding::default(), mode)?)
+    }
+
+    fn part_one(rounds: &Self::Input) -> anyhow::Result<u32> {
+        Ok(part_one(rounds, &Encoding::default())?)
+    }
+
+    fn part_two(rounds: &Self::Input) -> anyhow::Result<u32> {
+        Ok(part_two(rounds, &Encoding::default())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn sample_strategy_guide() -> anyhow::Result<()> {
+        let sample = "\
+A Y
+B X
+C Z
+";
+
+        let guide = Day02::parse(sample)?;
+        assert_eq!(Day02::part_one(&guide)?, 15);
+        assert_eq!(Day02::part_two(&guide)?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn rock_paper_scissors_preset_keeps_puzzle_scores() {
+        use Move::*;
+        let expected = [
+            ((Rock, Rock), 4),
+            ((Rock, Paper), 8),
+            ((Rock, Scissors), 3),
+            ((Paper, Rock), 1),
+            ((Paper, Paper), 5),
+            ((Paper, Scissors), 9),
+            ((Scissors, Rock), 7),
+            ((Scissors, Paper), 2),
+            ((Scissors, Scissors), 6),
+        ];
+        for ((opponent, me), score) in expected {
+            assert_eq!(round_score(opponent, me), score);
+        }
+        assert_eq!(required_move(Rock, Outcome::Win), Paper);
+        assert_eq!(required_move(Rock, Outcome::Lose), Scissors);
+        assert_eq!(required_move(Scissors, Outcome::Win), Rock);
+    }
+
+    #[test]
+    fn parse_errors_point_at_token() {
+        let err = parse_strategy_guide("A Y\n\nB Q\n", &Encoding::default(), ParseMode::Lenient)
+            .unwrap_err();
+        assert_eq!(err.line(), 3);
+        assert_eq!(
+            err.to_string(),
+            "line 3: invalid move or outcome \"Q\", expected one of A, B, C, X, Y, Z\n  |\n3 | B Q\n  |   ^"
+        );
+
+        let err = parse_round(7, "C", &Encoding::default(), ParseMode::Lenient).unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::MissingToken {
+                line: 7,
+                field: Field::SecondColumn,
+                ..
+            }
+        ));
+
+        // Both parts read the same parsed rounds, so they fail or succeed together.
+        let rounds =
+            parse_strategy_guide("C X\n\n\nA Z\n", &Encoding::default(), ParseMode::Lenient)
+                .unwrap();
+        assert_eq!(
+            rounds[1],
+            Round {
+                line: 4,
+                opponent: Move::Rock,
+                column2: "Z".to_string(),
+                snippet: Snippet::new(4, "A Z", 2, 1),
+            }
+        );
+        let encoding = Encoding::default();
+        assert_eq!(rounds[1].my_move(Part::One, &encoding), Ok(Move::Scissors));
+        assert_eq!(rounds[1].my_move(Part::Two, &encoding), Ok(Move::Paper));
+    }
+
+    #[test]
+    fn tokens_that_read_one_way_only_fail_the_other_part() -> anyhow::Result<()> {
+        let encoding = Encoding {
+            my_move: encoding::parse_column("rock=rock,paper=paper,scissors=scissors")?,
+            ..Encoding::default()
+        };
+        encoding.validate()?;
+        let guide = parse_strategy_guide(
+            "A paper\nB rock\nC scissors\n",
+            &encoding,
+            ParseMode::Lenient,
+        )?;
+        assert_eq!(part_one(&guide, &encoding), Ok(15));
+        assert!(matches!(
+            part_two(&guide, &encoding),
+            Err(ParseError::InvalidToken {
+                line: 1,
+                field: Field::DesiredOutcome,
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn strict_mode_rejects_what_lenient_mode_skips() -> anyhow::Result<()> {
+        let guide = "A Y extra\n\n\tB X\nC  Z\n";
+        assert_eq!(Day02::parse(guide)?.len(), 3);
+
+        let report = diagnose_strategy_guide(guide, &Encoding::default(), ParseMode::Strict);
+        let found: Vec<_> = report
+            .errors
+            .iter()
+            .map(|err| match err {
+                ParseError::Irregular {
+                    line, irregularity, ..
+                } => (*line, *irregularity),
+                other => panic!("unexpected error {other}"),
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, Irregularity::TrailingToken),
+                (2, Irregularity::ExtraBlankLine),
+                (3, Irregularity::Tab),
+                (4, Irregularity::StrayWhitespace),
+            ]
+        );
+        assert_eq!(
+            report.errors[0].to_string(),
+            "line 1: trailing token is not allowed in strict mode\n  |\n1 | A Y extra\n  |     ^^^^^"
+        );
+        assert_eq!(
+            Day02::parse_with("A Y\nB X\nC Z\n", ParseMode::Strict)?.len(),
+            3
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostics_collect_every_problem() {
+        let report = diagnose_strategy_guide(
+            "A Y\nQ\nB A\nC Z\nD W\n",
+            &Encoding::default(),
+            ParseMode::Lenient,
+        );
+        let found: Vec<_> = report
+            .errors
+            .iter()
+            .map(|err| match err {
+                ParseError::InvalidToken { line, field, .. }
+                | ParseError::MissingToken { line, field, .. } => (*line, *field),
+                ParseError::Irregular { .. } => panic!("lenient mode accepts any layout"),
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, Field::OpponentMove),
+                (2, Field::SecondColumn),
+                (3, Field::DesiredOutcome),
+                (5, Field::OpponentMove),
+                (5, Field::SecondColumn),
+            ]
+        );
+        assert_eq!(
+            report.summary(),
+            vec![("invalid token", 4), ("missing token", 1)]
+        );
+    }
+
+    fn any_move() -> impl proptest::strategy::Strategy<Value = Move> {
+        prop::sample::select(Move::ALL.to_vec())
+    }
+
+    fn any_outcome() -> impl proptest::strategy::Strategy<Value = Outcome> {
+        prop::sample::select(vec![Outcome::Lose, Outcome::Draw, Outcome::Win])
+    }
+
+    proptest! {
+        #[test]
+        fn required_move_always_reaches_the_desired_outcome(
+            opponent in any_move(),
+            desired in any_outcome(),
+        ) {
+            let me = required_move(opponent, desired);
+            prop_assert_eq!(outcome(opponent, me), desired);
+            let game = Game::ROCK_PAPER_SCISSORS;
+            prop_assert_eq!(
+                round_score(opponent, me),
+                game.outcome_score(desired) + me.shape_score()
+            );
+        }
+
+        #[test]
+        fn every_round_scores_between_one_and_nine(opponent in any_move(), me in any_move()) {
+            prop_assert!((1..=9).contains(&round_score(opponent, me)));
+        }
+
+        #[test]
+        fn moves_round_trip_through_names_and_guides(
+            rounds in prop::collection::vec((any_move(), any_move()), 0..30),
+        ) {
+            for &(opponent, me) in &rounds {
+                prop_assert_eq!(opponent.to_string().parse::<Move>(), Ok(opponent));
+                prop_assert_eq!(me.to_string().parse::<Move>(), Ok(me));
+            }
+            let index = |m: Move| Move::ALL.iter().position(|&n| n == m).unwrap();
+            let guide: String = rounds
+                .iter()
+                .map(|&(opponent, me)| {
+                    let (a, x) = (["A", "B", "C"][index(opponent)], ["X", "Y", "Z"][index(me)]);
+                    format!("{a} {x}\n")
+                })
+                .collect();
+            let parsed =
+                parse_strategy_guide(&guide, &Encoding::default(), ParseMode::Strict).unwrap();
+            let read: Vec<_> = parsed
+                .iter()
+                .map(|round| (round.opponent, round.my_move(Part::One, &Encoding::default()).unwrap()))
+                .collect();
+            prop_assert_eq!(read, rounds);
+        }
+    }
+}
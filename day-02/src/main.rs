@@ -1,139 +1,264 @@
-use std::fs;
-use std::path::Path;
-use std::str::FromStr;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Instant;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Move {
-    Rock,
-    Paper,
-    Scissors,
-}
+use anyhow::{Result, bail};
+use aoc_core::{Format, InputSource, ParseMode, Part, Record, Solution, input_hash};
+use clap::{Parser, Subcommand};
+use day_02::encoding::parse_column;
+use day_02::optimal::Letters;
+use day_02::trace::{TraceTable, to_csv};
+use day_02::{
+    Constraints, Day02, Encoding, Move, Outcome, RuleSet, ScoringRules, StrategySpec, Tournament,
+    TraceFormat, bounds, compare, diagnose_strategy_guide, explore, parse_strategy_guide, part_one,
+    part_two, trace,
+};
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Outcome {
-    Lose,
-    Draw,
-    Win,
+/// Day 2: Rock Paper Scissors.
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Puzzle input (`-` for stdin); defaults to `AOC_INPUT`, then `input.txt`.
+    input: Option<String>,
+    /// Validate the whole guide and report every problem instead of solving.
+    #[arg(long)]
+    check: bool,
+    /// TOML or JSON file mapping each column's tokens to moves and outcomes.
+    #[arg(long, global = true)]
+    encoding: Option<PathBuf>,
+    /// First-column tokens, e.g. `A=rock,B=paper,C=scissors`; overrides `--encoding`.
+    #[arg(long, global = true, value_parser = parse_column::<Move>)]
+    opponent_tokens: Option<BTreeMap<String, Move>>,
+    /// Second-column tokens read as my move, e.g. `X=rock,Y=paper,Z=scissors`.
+    #[arg(long, global = true, value_parser = parse_column::<Move>)]
+    move_tokens: Option<BTreeMap<String, Move>>,
+    /// Second-column tokens read as an outcome, e.g. `X=lose,Y=draw,Z=win`.
+    #[arg(long, global = true, value_parser = parse_column::<Outcome>)]
+    outcome_tokens: Option<BTreeMap<String, Outcome>>,
+    /// How to treat irregular layout such as tabs, extra tokens or blank lines: lenient or strict.
+    #[arg(long, global = true, default_value = "lenient")]
+    mode: ParseMode,
+    /// Print every round of both parts with its score and running total: text, csv or json.
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "text",
+        conflicts_with_all = ["check", "explore", "rules"]
+    )]
+    trace: Option<TraceFormat>,
+    /// Score every reading of the second column as moves or outcomes, best first.
+    #[arg(long, conflicts_with_all = ["check", "rules"])]
+    explore: bool,
+    /// TOML or JSON file of alternative scoring rules to compare against the puzzle's own.
+    #[arg(long, conflicts_with = "check")]
+    rules: Option<PathBuf>,
+    /// Output format for the answers: text or json (one record per part).
+    #[arg(long, default_value = "text", conflicts_with_all = ["check", "trace", "explore", "rules"])]
+    format: Format,
 }
 
-impl FromStr for Move {
-    type Err = String;
+#[derive(Subcommand)]
+enum Command {
+    /// Play two strategies against each other over many seeded matches and report how it went.
+    Simulate {
+        /// Guide the `guide` strategies follow (`-` for stdin); defaults as for solving.
+        input: Option<String>,
+        /// My strategy: guide, guide-opponent, random, frequency, beat-last, rock, paper or scissors.
+        #[arg(long, default_value = "guide")]
+        me: StrategySpec,
+        /// The opponent's strategy, from the same list.
+        #[arg(long, default_value = "frequency")]
+        opponent: StrategySpec,
+        /// Which part's reading of the guide the `guide` strategy follows.
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Number of matches to play.
+        #[arg(long, default_value_t = 1000)]
+        matches: usize,
+        /// Rounds per match; defaults to the length of the guide, or 100 without one.
+        #[arg(long)]
+        rounds: Option<usize>,
+        /// Seed for the random number generator.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Find the highest and lowest scores possible against the guide's opponent column.
+    Optimal {
+        /// Guide whose opponent column to play against (`-` for stdin); defaults as for solving.
+        input: Option<String>,
+        /// Lose exactly this many rounds.
+        #[arg(long)]
+        lose: Option<usize>,
+        /// Never play the same move more than this many rounds in a row.
+        #[arg(long)]
+        max_run: Option<usize>,
+        /// Also print each plan's moves, one letter (R, P or S) per round.
+        #[arg(long)]
+        moves: bool,
+    },
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "A" | "X" => Ok(Move::Rock),
-            "B" | "Y" => Ok(Move::Paper),
-            "C" | "Z" => Ok(Move::Scissors),
-            other => Err(format!("Invalid move token: {}", other)),
+impl Args {
+    /// The encoding file, if any, with the columns given as flags laid over it.
+    fn encoding(&self) -> Result<Encoding> {
+        let mut encoding = match &self.encoding {
+            Some(path) => Encoding::load(path)?,
+            None => Encoding::default(),
+        };
+        if let Some(tokens) = &self.opponent_tokens {
+            encoding.opponent = tokens.clone();
+        }
+        if let Some(tokens) = &self.move_tokens {
+            encoding.my_move = tokens.clone();
         }
+        if let Some(tokens) = &self.outcome_tokens {
+            encoding.outcome = tokens.clone();
+        }
+        encoding.validate()?;
+        Ok(encoding)
     }
 }
 
-fn parse_outcome(token: &str) -> Result<Outcome, String> {
-    match token {
-        "X" => Ok(Outcome::Lose),
-        "Y" => Ok(Outcome::Draw),
-        "Z" => Ok(Outcome::Win),
-        other => Err(format!("Invalid outcome token: {}", other)),
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let encoding = args.encoding()?;
+
+    if let Some(Command::Simulate {
+        input,
+        me,
+        opponent,
+        part,
+        matches,
+        rounds,
+        seed,
+    }) = &args.command
+    {
+        let part = if *part == 1 { Part::One } else { Part::Two };
+        // Only read a guide if one of the strategies follows it.
+        let guide = if me.needs_guide() || opponent.needs_guide() {
+            let source = InputSource::resolve(input.as_deref(), Day02::DEFAULT_INPUT);
+            let guide = parse_strategy_guide(&source.read()?, &encoding, args.mode)?;
+            if guide.is_empty() {
+                bail!("The strategy guide in {source} has no rounds to follow");
+            }
+            guide
+        } else {
+            Vec::new()
+        };
+        let tournament = Tournament {
+            matches: *matches,
+            rounds: rounds.unwrap_or(if guide.is_empty() { 100 } else { guide.len() }),
+            seed: *seed,
+        };
+        let report = tournament.run(
+            me.build(&guide, &encoding, part)?.as_ref(),
+            opponent.build(&guide, &encoding, part)?.as_ref(),
+        );
+        print!("{report}");
+        return Ok(());
     }
-}
 
-impl Move {
-    fn shape_score(self) -> u32 {
-        match self {
-            Move::Rock => 1,
-            Move::Paper => 2,
-            Move::Scissors => 3,
+    if let Some(Command::Optimal {
+        input,
+        lose,
+        max_run,
+        moves,
+    }) = &args.command
+    {
+        let source = InputSource::resolve(input.as_deref(), Day02::DEFAULT_INPUT);
+        let guide = parse_strategy_guide(&source.read()?, &encoding, args.mode)?;
+        let opponents: Vec<Move> = guide.iter().map(|round| round.opponent).collect();
+        let constraints = Constraints {
+            losses: *lose,
+            max_run: *max_run,
+        };
+        let Some(found) = bounds(&opponents, constraints) else {
+            bail!("No sequence of moves against {source} meets those constraints");
+        };
+        for (label, plan) in [("Highest", &found.max), ("Lowest", &found.min)] {
+            println!("{label} possible score: {}", plan.score);
+            if *moves {
+                println!("{}", Letters(&plan.moves));
+            }
         }
+        return Ok(());
     }
-}
 
-fn round_score(opponent: Move, me: Move) -> u32 {
-    let outcome_score = match (me, opponent) {
-        (a, b) if a == b => 3, // draw
-        (Move::Rock, Move::Scissors)
-        | (Move::Scissors, Move::Paper)
-        | (Move::Paper, Move::Rock) => 6, // win
-        _ => 0,                // loss
-    };
+    let contents = InputSource::resolve(args.input.as_deref(), Day02::DEFAULT_INPUT).read()?;
 
-    outcome_score + me.shape_score()
-}
-
-fn required_move(opponent: Move, desired: Outcome) -> Move {
-    match desired {
-        Outcome::Draw => opponent,
-        Outcome::Win => match opponent {
-            Move::Rock => Move::Paper,
-            Move::Paper => Move::Scissors,
-            Move::Scissors => Move::Rock,
-        },
-        Outcome::Lose => match opponent {
-            Move::Rock => Move::Scissors,
-            Move::Paper => Move::Rock,
-            Move::Scissors => Move::Paper,
-        },
+    if args.check {
+        let report = diagnose_strategy_guide(&contents, &encoding, args.mode);
+        if !report.is_empty() {
+            bail!("{report}");
+        }
+        println!("{report}");
+        return Ok(());
     }
-}
 
-fn parse_round(line: &str) -> Result<(Move, Move), String> {
-    let mut parts = line.split_whitespace();
-    let opponent = parts
-        .next()
-        .ok_or_else(|| format!("Missing opponent move in line: {}", line))?
-        .parse::<Move>()?;
-    let me = parts
-        .next()
-        .ok_or_else(|| format!("Missing my move in line: {}", line))?
-        .parse::<Move>()?;
-    Ok((opponent, me))
-}
+    let start = Instant::now();
+    let guide = parse_strategy_guide(&contents, &encoding, args.mode)?;
+    let parsed = start.elapsed();
 
-fn main() {
-    let input_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("input.txt");
-    let contents = fs::read_to_string(&input_path).unwrap_or_else(|err| {
-        eprintln!("Failed to read {}: {}", input_path.display(), err);
-        std::process::exit(1);
-    });
+    if let Some(format) = args.trace {
+        let parts = [
+            trace(&guide, &encoding, Part::One)?,
+            trace(&guide, &encoding, Part::Two)?,
+        ];
+        match format {
+            TraceFormat::Text => {
+                for (part, rounds) in Part::ALL.iter().zip(&parts) {
+                    println!("Part {}", part.number());
+                    println!("{}", TraceTable(rounds));
+                }
+            }
+            TraceFormat::Csv => print!("{}", to_csv(&parts.concat())),
+            TraceFormat::Json => println!("{}", serde_json::to_string_pretty(&parts.concat())?),
+        }
+        return Ok(());
+    }
 
-    let total_score: u32 = contents
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| {
-            let (opponent, me) = parse_round(line).unwrap_or_else(|err| {
-                eprintln!("{}", err);
-                std::process::exit(1);
-            });
-            round_score(opponent, me)
-        })
-        .sum();
+    if args.explore {
+        print!("{}", explore(&guide, &encoding)?);
+        return Ok(());
+    }
 
-    let total_score_part2: u32 = contents
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            let opponent = parts
-                .next()
-                .ok_or_else(|| format!("Missing opponent move in line: {}", line))
-                .and_then(|tok| tok.parse::<Move>())
-                .unwrap_or_else(|err| {
-                    eprintln!("{}", err);
-                    std::process::exit(1);
-                });
-            let desired = parts
-                .next()
-                .ok_or_else(|| format!("Missing desired outcome in line: {}", line))
-                .and_then(parse_outcome)
-                .unwrap_or_else(|err| {
-                    eprintln!("{}", err);
-                    std::process::exit(1);
-                });
-            let my_move = required_move(opponent, desired);
-            round_score(opponent, my_move)
-        })
-        .sum();
+    if let Some(path) = &args.rules {
+        let mut sets = vec![RuleSet {
+            name: "puzzle".to_string(),
+            rules: ScoringRules::PUZZLE,
+        }];
+        sets.extend(RuleSet::load(path)?);
+        print!("{}", compare(&guide, &encoding, &sets)?);
+        return Ok(());
+    }
+
+    if args.format == Format::Json {
+        let hash = input_hash(contents.as_bytes());
+        for part in Part::ALL {
+            // Parsing is shared by both parts, so each record counts it on top of its own time.
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => part_one(&guide, &encoding)?,
+                Part::Two => part_two(&guide, &encoding)?,
+            };
+            let record = Record::new(Day02::DAY, part, answer, parsed + start.elapsed(), hash);
+            println!("{}", serde_json::to_string(&record)?);
+        }
+        return Ok(());
+    }
 
-    println!("Total score (Part 1 logic): {}", total_score);
-    println!("Total score (Part 2 logic): {}", total_score_part2);
+    // A partial encoding may only read the guide one way, so Part 1 is printed before Part 2
+    // gets the chance to fail.
+    println!(
+        "Total score (Part 1 logic): {}",
+        part_one(&guide, &encoding)?
+    );
+    println!(
+        "Total score (Part 2 logic): {}",
+        part_two(&guide, &encoding)?
+    );
+    Ok(())
 }
@@ -0,0 +1,328 @@
+use std::fmt;
+
+use crate::{Move, Outcome, outcome, round_score};
+
+/// Limits on which move sequences count when searching for the best or worst one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Constraints {
+    /// Lose exactly this many rounds.
+    pub losses: Option<usize>,
+    /// Never play the same move more than this many rounds in a row.
+    pub max_run: Option<usize>,
+}
+
+/// Whether to look for the highest or the lowest score.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Goal {
+    Max,
+    Min,
+}
+
+impl Goal {
+    fn prefers(self, candidate: u32, current: u32) -> bool {
+        match self {
+            Goal::Max => candidate > current,
+            Goal::Min => candidate < current,
+        }
+    }
+}
+
+/// A move for every round and the total score they earn.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Plan {
+    pub score: u32,
+    pub moves: Vec<Move>,
+}
+
+/// A state after some round: losses so far, the move just played and how many times in a row.
+#[derive(Clone, Copy)]
+struct State {
+    losses: usize,
+    last: usize,
+    run: usize,
+}
+
+/// Sizes of the state space a [`Constraints`] needs; unconstrained dimensions collapse to one.
+struct Space {
+    losses: usize,
+    runs: usize,
+}
+
+impl Space {
+    /// The space for `rounds` rounds; a run can't be longer than the game.
+    fn new(constraints: Constraints, rounds: usize) -> Self {
+        Space {
+            losses: constraints.losses.map_or(1, |k| k + 1),
+            runs: constraints.max_run.map_or(1, |m| m.min(rounds)),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.losses * Move::ALL.len() * self.runs
+    }
+
+    fn state(&self, index: usize) -> State {
+        State {
+            losses: index / self.runs / Move::ALL.len(),
+            last: index / self.runs % Move::ALL.len(),
+            run: index % self.runs + 1,
+        }
+    }
+
+    fn index(&self, state: State) -> usize {
+        (state.losses * Move::ALL.len() + state.last) * self.runs + state.run - 1
+    }
+
+    /// Index of a run's first state among those with `run == 1`.
+    fn start(&self, state: State) -> usize {
+        state.losses * Move::ALL.len() + state.last
+    }
+}
+
+/// Finds the move sequence against `opponents` that best meets `goal` within `constraints`.
+///
+/// Dynamic programming over rounds, tracking losses so far and the current run of the same
+/// move only when a constraint needs them. Returns `None` when no sequence satisfies the
+/// constraints. Ties go to the earliest move in [`Move::ALL`].
+pub fn solve(opponents: &[Move], constraints: Constraints, goal: Goal) -> Option<Plan> {
+    if opponents.is_empty() {
+        return (constraints.losses.unwrap_or(0) == 0).then(|| Plan {
+            score: 0,
+            moves: Vec::new(),
+        });
+    }
+    if constraints.max_run == Some(0) || constraints.losses > Some(opponents.len()) {
+        return None;
+    }
+
+    let space = Space::new(constraints, opponents.len());
+    let lost = |i: usize, last: usize| match constraints.losses {
+        Some(_) => usize::from(outcome(opponents[i], Move::ALL[last]) == Outcome::Lose),
+        None => 0,
+    };
+    // Only the previous round's scores are needed to extend it. A state that continues a run
+    // can only come from the same move's shorter run, so back-pointers are only kept for
+    // states that start one: the previous round's move and run length.
+    let mut scores: Vec<Option<u32>> = Vec::new();
+    let mut starts: Vec<Vec<(u8, u32)>> = Vec::with_capacity(opponents.len());
+
+    for (i, &opponent) in opponents.iter().enumerate() {
+        let mut layer = vec![None; space.len()];
+        let mut from = vec![(0, 0); space.losses * Move::ALL.len()];
+        let mut offer = |state: State, score: u32, parent: Option<State>| {
+            let index = space.index(state);
+            if layer[index].is_none_or(|current| goal.prefers(score, current)) {
+                layer[index] = Some(score);
+                if let (1, Some(parent)) = (state.run, parent) {
+                    // A move index fits a `u8`, and a run no longer than the guide a `u32`.
+                    from[space.start(state)] = (parent.last as u8, parent.run as u32);
+                }
+            }
+        };
+
+        for (mv, &me) in Move::ALL.iter().enumerate() {
+            let points = round_score(opponent, me);
+            let mut extend = |prev: Option<State>, score: u32| {
+                let run = match prev {
+                    Some(p) if p.last == mv && constraints.max_run.is_some() => p.run + 1,
+                    _ => 1,
+                };
+                let losses = prev.map_or(0, |p| p.losses) + lost(i, mv);
+                if run <= space.runs && losses < space.losses {
+                    offer(
+                        State {
+                            losses,
+                            last: mv,
+                            run,
+                        },
+                        score + points,
+                        prev,
+                    );
+                }
+            };
+            if i == 0 {
+                extend(None, 0);
+                continue;
+            }
+            for (parent, score) in scores.iter().enumerate() {
+                if let Some(score) = *score {
+                    extend(Some(space.state(parent)), score);
+                }
+            }
+        }
+        scores = layer;
+        starts.push(from);
+    }
+
+    // Pick the best final state, then walk the back-pointers to the first round.
+    let wanted_losses = constraints.losses.unwrap_or(0);
+    let mut best: Option<(u32, State)> = None;
+    for last in 0..Move::ALL.len() {
+        for run in 1..=space.runs {
+            let state = State {
+                losses: wanted_losses,
+                last,
+                run,
+            };
+            if let Some(score) = scores[space.index(state)]
+                && best.is_none_or(|(current, _)| goal.prefers(score, current))
+            {
+                best = Some((score, state));
+            }
+        }
+    }
+    let (score, mut state) = best?;
+    let mut moves = vec![Move::Rock; opponents.len()];
+    for i in (1..opponents.len()).rev() {
+        moves[i] = Move::ALL[state.last];
+        let losses = state.losses - lost(i, state.last);
+        state = if state.run > 1 {
+            State {
+                losses,
+                last: state.last,
+                run: state.run - 1,
+            }
+        } else {
+            let (last, run) = starts[i][space.start(state)];
+            State {
+                losses,
+                last: usize::from(last),
+                run: run as usize,
+            }
+        };
+    }
+    moves[0] = Move::ALL[state.last];
+    Some(Plan { score, moves })
+}
+
+/// The highest and lowest scores achievable against one opponent column.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Bounds {
+    pub max: Plan,
+    pub min: Plan,
+}
+
+/// Solves for both [`Goal`]s at once; `None` if the constraints cannot be met.
+pub fn bounds(opponents: &[Move], constraints: Constraints) -> Option<Bounds> {
+    Some(Bounds {
+        max: solve(opponents, constraints, Goal::Max)?,
+        min: solve(opponents, constraints, Goal::Min)?,
+    })
+}
+
+/// A plan's moves as one letter each: `R`, `P` or `S`.
+pub struct Letters<'a>(pub &'a [Move]);
+
+impl fmt::Display for Letters<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for m in self.0 {
+            let letter = match m {
+                Move::Rock => 'R',
+                Move::Paper => 'P',
+                Move::Scissors => 'S',
+            };
+            write!(f, "{letter}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every move sequence of the same length as `opponents`, with its score.
+    fn brute_force(opponents: &[Move]) -> Vec<(u32, Vec<Move>)> {
+        let mut all = vec![(0, Vec::new())];
+        for &opponent in opponents {
+            all = all
+                .into_iter()
+                .flat_map(|(score, moves): (u32, Vec<Move>)| {
+                    Move::ALL.map(|me| {
+                        let mut moves = moves.clone();
+                        moves.push(me);
+                        (score + round_score(opponent, me), moves)
+                    })
+                })
+                .collect();
+        }
+        all
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force_under_every_constraint() {
+        use Move::*;
+        let opponents = [Rock, Rock, Paper, Rock, Scissors, Scissors];
+        let all = brute_force(&opponents);
+        let losses = |moves: &[Move]| {
+            opponents
+                .iter()
+                .zip(moves)
+                .filter(|&(&o, &m)| outcome(o, m) == Outcome::Lose)
+                .count()
+        };
+        let longest_run = |moves: &[Move]| {
+            moves
+                .chunk_by(|a, b| a == b)
+                .map(<[Move]>::len)
+                .max()
+                .unwrap_or(0)
+        };
+
+        for k in [None, Some(0), Some(2), Some(6), Some(7)] {
+            for m in [None, Some(1), Some(2)] {
+                let constraints = Constraints {
+                    losses: k,
+                    max_run: m,
+                };
+                let allowed: Vec<_> = all
+                    .iter()
+                    .filter(|(_, moves)| k.is_none_or(|k| losses(moves) == k))
+                    .filter(|(_, moves)| m.is_none_or(|m| longest_run(moves) <= m))
+                    .collect();
+                let found = bounds(&opponents, constraints);
+                let (Some(max), Some(min)) = (
+                    allowed.iter().map(|(s, _)| *s).max(),
+                    allowed.iter().map(|(s, _)| *s).min(),
+                ) else {
+                    assert_eq!(found, None, "{constraints:?}");
+                    continue;
+                };
+                let found = found.unwrap();
+                assert_eq!((found.max.score, found.min.score), (max, min));
+                for plan in [&found.max, &found.min] {
+                    assert!(
+                        allowed
+                            .iter()
+                            .any(|(s, moves)| (*s, moves) == (plan.score, &plan.moves))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn huge_constraints_are_settled_without_sizing_a_state_space() {
+        use Move::*;
+        let opponents = [Rock, Rock, Paper, Rock, Scissors, Scissors];
+        let huge = Constraints {
+            losses: Some(400_000_000),
+            max_run: None,
+        };
+        assert_eq!(bounds(&opponents, huge), None);
+        let huge = Constraints {
+            losses: None,
+            max_run: Some(1_000_000_000),
+        };
+        assert_eq!(
+            bounds(&opponents, huge),
+            bounds(&opponents, Constraints::default())
+        );
+    }
+
+    #[test]
+    fn plans_print_as_one_letter_per_move() {
+        use Move::*;
+        assert_eq!(Letters(&[Rock, Paper, Scissors]).to_string(), "RPS");
+    }
+}
@@ -0,0 +1,324 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use aoc_core::Part;
+
+use crate::error::ScoringError;
+use crate::{Encoding, Move, Outcome, Round, outcome};
+
+/// Points awarded for each shape played and each way a round can end.
+///
+/// Any field left out of a config keeps the puzzle's value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringRules {
+    pub rock: u32,
+    pub paper: u32,
+    pub scissors: u32,
+    pub lose: u32,
+    pub draw: u32,
+    pub win: u32,
+}
+
+impl ScoringRules {
+    /// The puzzle's rules: 1/2/3 for rock/paper/scissors and 0/3/6 for a loss/draw/win.
+    pub const PUZZLE: ScoringRules = ScoringRules {
+        rock: 1,
+        paper: 2,
+        scissors: 3,
+        lose: 0,
+        draw: 3,
+        win: 6,
+    };
+
+    /// Points awarded for playing `shape`.
+    pub fn shape_score(&self, shape: Move) -> u32 {
+        match shape {
+            Move::Rock => self.rock,
+            Move::Paper => self.paper,
+            Move::Scissors => self.scissors,
+        }
+    }
+
+    /// Points awarded for ending a round with `outcome`.
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Lose => self.lose,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+
+    /// Total score for a single round: outcome points plus the points for my shape.
+    ///
+    /// Both come from config as `u32`, so they are added as `u64` to never overflow.
+    pub fn round_score(&self, opponent: Move, me: Move) -> u64 {
+        u64::from(self.outcome_score(outcome(opponent, me))) + u64::from(self.shape_score(me))
+    }
+
+    /// Total score of `rounds` under these rules and `part`'s reading of the second column
+    /// through `encoding`.
+    pub fn score(
+        &self,
+        rounds: &[Round],
+        encoding: &Encoding,
+        part: Part,
+    ) -> Result<u64, ScoringError> {
+        rounds.iter().try_fold(0u64, |total, round| {
+            let points = self.round_score(round.opponent, round.my_move(part, encoding)?);
+            (total.checked_add(points)).ok_or(ScoringError::Overflow(part.number()))
+        })
+    }
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self::PUZZLE
+    }
+}
+
+/// A named set of [`ScoringRules`], as listed in a rules file.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+#[serde(try_from = "RuleSetEntry")]
+pub struct RuleSet {
+    pub name: String,
+    pub rules: ScoringRules,
+}
+
+/// A rule set as written in a file: its name alongside the rules' own fields.
+///
+/// `#[serde(flatten)]` would accept misspelt fields silently, so this spells them out.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RuleSetEntry {
+    name: Option<String>,
+    rock: u32,
+    paper: u32,
+    scissors: u32,
+    lose: u32,
+    draw: u32,
+    win: u32,
+}
+
+impl Default for RuleSetEntry {
+    fn default() -> Self {
+        let ScoringRules {
+            rock,
+            paper,
+            scissors,
+            lose,
+            draw,
+            win,
+        } = ScoringRules::PUZZLE;
+        RuleSetEntry {
+            name: None,
+            rock,
+            paper,
+            scissors,
+            lose,
+            draw,
+            win,
+        }
+    }
+}
+
+impl TryFrom<RuleSetEntry> for RuleSet {
+    type Error = &'static str;
+
+    fn try_from(entry: RuleSetEntry) -> Result<Self, Self::Error> {
+        let RuleSetEntry {
+            name,
+            rock,
+            paper,
+            scissors,
+            lose,
+            draw,
+            win,
+        } = entry;
+        Ok(RuleSet {
+            name: name.ok_or("every rule set needs a `name`")?,
+            rules: ScoringRules {
+                rock,
+                paper,
+                scissors,
+                lose,
+                draw,
+                win,
+            },
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    rules: Vec<RuleSet>,
+}
+
+impl RuleSet {
+    /// Loads every rule set from a file, in order: JSON if it ends in `.json`, TOML otherwise.
+    ///
+    /// Both hold a list of named sets under `rules`, e.g. in TOML:
+    ///
+    /// ```toml
+    /// [[rules]]
+    /// name = "winner takes all"
+    /// rock = 0
+    /// paper = 0
+    /// scissors = 0
+    /// win = 1
+    /// draw = 0
+    /// ```
+    pub fn load(path: &Path) -> anyhow::Result<Vec<RuleSet>> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let file: RulesFile = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&contents).map_err(anyhow::Error::from)
+        } else {
+            toml::from_str(&contents).map_err(anyhow::Error::from)
+        }
+        .with_context(|| format!("Invalid scoring rules in {}", path.display()))?;
+        Ok(file.rules)
+    }
+}
+
+/// Both parts' totals for one guide under several rule sets, side by side.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Comparison {
+    /// `(rule set name, part 1 total, part 2 total)`, in the order the sets were given.
+    pub rows: Vec<(String, u64, u64)>,
+}
+
+/// Scores `rounds`, read through `encoding`, under every rule set in `sets`.
+pub fn compare(
+    rounds: &[Round],
+    encoding: &Encoding,
+    sets: &[RuleSet],
+) -> Result<Comparison, ScoringError> {
+    let rows = sets
+        .iter()
+        .map(|set| {
+            Ok((
+                set.name.clone(),
+                set.rules.score(rounds, encoding, Part::One)?,
+                set.rules.score(rounds, encoding, Part::Two)?,
+            ))
+        })
+        .collect::<Result<_, ScoringError>>()?;
+    Ok(Comparison { rows })
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name_width = self
+            .rows
+            .iter()
+            .map(|(name, ..)| name.chars().count())
+            .chain(["rules".len()])
+            .max()
+            .unwrap_or(0);
+        // Wide enough for the headers and for any u32, and wider for larger totals.
+        let total_width = self
+            .rows
+            .iter()
+            .flat_map(|(_, one, two)| [one, two])
+            .map(|total| total.to_string().len())
+            .chain([10])
+            .max()
+            .unwrap_or(10);
+        writeln!(
+            f,
+            "{:<name_width$}  {:>total_width$}  {:>total_width$}",
+            "rules", "part 1", "part 2"
+        )?;
+        for (name, part_one, part_two) in &self.rows {
+            writeln!(
+                f,
+                "{name:<name_width$}  {part_one:>total_width$}  {part_two:>total_width$}"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day02, Game, round_score};
+    use aoc_core::Solution;
+
+    #[test]
+    fn rule_sets_score_the_same_guide_side_by_side() -> anyhow::Result<()> {
+        for opponent in [Move::Rock, Move::Paper, Move::Scissors] {
+            for me in [Move::Rock, Move::Paper, Move::Scissors] {
+                assert_eq!(
+                    ScoringRules::PUZZLE.round_score(opponent, me),
+                    u64::from(round_score(opponent, me))
+                );
+            }
+        }
+        assert_eq!(Game::ROCK_PAPER_SCISSORS.outcome_score(Outcome::Win), 6);
+
+        let file: RulesFile = toml::from_str(
+            r#"
+            [[rules]]
+            name = "puzzle"
+
+            [[rules]]
+            name = "winner takes all"
+            rock = 0
+            paper = 0
+            scissors = 0
+            win = 1
+            draw = 0
+            "#,
+        )?;
+        let guide = Day02::parse("A Y\nB X\nC Z\n")?;
+        let comparison = compare(&guide, &Encoding::default(), &file.rules)?;
+        assert_eq!(
+            comparison.rows,
+            vec![
+                ("puzzle".to_string(), 15, 12),
+                ("winner takes all".to_string(), 1, 1),
+            ]
+        );
+        assert_eq!(
+            comparison.to_string(),
+            "\
+rules                 part 1      part 2
+puzzle                    15          12
+winner takes all           1           1
+"
+        );
+
+        let typo = toml::from_str::<RulesFile>("[[rules]]\nname = \"typo\"\nwins = 3\n");
+        assert!(typo.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn huge_points_add_up_without_overflowing() -> anyhow::Result<()> {
+        let file: RulesFile = toml::from_str(
+            r#"
+            [[rules]]
+            name = "huge"
+            win = 4294967295
+            scissors = 4294967295
+            "#,
+        )?;
+        let guide = Day02::parse("A Y\nB X\nC Z\n")?;
+        let comparison = compare(&guide, &Encoding::default(), &file.rules)?;
+        let max = u64::from(u32::MAX);
+        // Part 1 wins once and plays scissors once; Part 2 plays rock throughout, winning once.
+        assert_eq!(
+            comparison.rows,
+            vec![("huge".to_string(), max + 2 + 1 + max + 3, 4 + 1 + max + 1)]
+        );
+        assert!(comparison.to_string().contains(&(2 * max + 6).to_string()));
+        Ok(())
+    }
+}
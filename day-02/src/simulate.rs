@@ -0,0 +1,429 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_core::Part;
+use rand::rngs::ChaCha8Rng;
+use rand::{RngExt, SeedableRng};
+
+use crate::error::StrategyError;
+use crate::{Encoding, Move, Outcome, Round, outcome, required_move, round_score};
+
+/// The seeded generator strategies draw from, so a tournament replays exactly from its seed.
+pub type SimRng = ChaCha8Rng;
+
+/// Everything a strategy sees before a round: both players' moves so far, oldest first.
+#[derive(Clone, Copy, Debug)]
+pub struct History<'a> {
+    pub mine: &'a [Move],
+    pub theirs: &'a [Move],
+    /// How often the opponent has played each of [`Move::ALL`] so far, kept as the match goes
+    /// so strategies needn't count `theirs` again every round.
+    pub their_counts: [usize; 3],
+}
+
+/// A way of picking moves round after round.
+pub trait Strategy {
+    /// Short name used in reports.
+    fn name(&self) -> String;
+
+    /// Picks the move for the next round.
+    fn next_move(&self, history: History<'_>, rng: &mut SimRng) -> Move;
+}
+
+/// The move that beats `m`.
+fn beating(m: Move) -> Move {
+    required_move(m, Outcome::Win)
+}
+
+/// Plays a fixed sequence of moves, starting over when it runs out.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Fixed {
+    name: String,
+    /// Never empty, so there's always a move to start over from.
+    moves: Vec<Move>,
+}
+
+impl Fixed {
+    pub fn new(name: String, moves: Vec<Move>) -> Result<Self, StrategyError> {
+        if moves.is_empty() {
+            return Err(StrategyError::NoMoves(name));
+        }
+        Ok(Fixed { name, moves })
+    }
+}
+
+impl Strategy for Fixed {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn next_move(&self, history: History<'_>, _: &mut SimRng) -> Move {
+        self.moves[history.mine.len() % self.moves.len()]
+    }
+}
+
+/// Picks uniformly at random every round.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Random;
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn next_move(&self, _: History<'_>, rng: &mut SimRng) -> Move {
+        Move::ALL[rng.random_range(0..Move::ALL.len())]
+    }
+}
+
+/// Beats the opponent's most frequent move so far, favouring the latest on a tie.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency".to_string()
+    }
+
+    fn next_move(&self, history: History<'_>, rng: &mut SimRng) -> Move {
+        let Some(&last) = history.theirs.last() else {
+            return Random.next_move(history, rng);
+        };
+        let favourite = Move::ALL
+            .into_iter()
+            .max_by_key(|&m| (history.their_counts[m.shape().index()], m == last))
+            .unwrap_or(last);
+        beating(favourite)
+    }
+}
+
+/// Plays whatever would have beaten the opponent's previous move.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BeatLast;
+
+impl Strategy for BeatLast {
+    fn name(&self) -> String {
+        "beat-last".to_string()
+    }
+
+    fn next_move(&self, history: History<'_>, rng: &mut SimRng) -> Move {
+        match history.theirs.last() {
+            Some(&last) => beating(last),
+            None => Random.next_move(history, rng),
+        }
+    }
+}
+
+/// A strategy named on the command line.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StrategySpec {
+    /// My moves from the guide, under one part's reading.
+    Guide,
+    /// The opponent column of the guide, i.e. the opponent the elf expected.
+    GuideOpponent,
+    Random,
+    Frequency,
+    BeatLast,
+    /// The same move every round.
+    Always(Move),
+}
+
+impl StrategySpec {
+    /// Whether building this strategy needs a parsed guide.
+    pub fn needs_guide(self) -> bool {
+        matches!(self, StrategySpec::Guide | StrategySpec::GuideOpponent)
+    }
+
+    /// Builds the strategy, taking moves from `rounds` read through `encoding` under `part` if
+    /// it follows the guide.
+    ///
+    /// Following an empty guide is an error, as there'd be no move to play.
+    pub fn build(
+        self,
+        rounds: &[Round],
+        encoding: &Encoding,
+        part: Part,
+    ) -> Result<Box<dyn Strategy>, StrategyError> {
+        Ok(match self {
+            StrategySpec::Guide => Box::new(Fixed::new(
+                format!("guide (part {})", part.number()),
+                rounds
+                    .iter()
+                    .map(|round| round.my_move(part, encoding))
+                    .collect::<Result<_, _>>()?,
+            )?),
+            StrategySpec::GuideOpponent => Box::new(Fixed::new(
+                "guide opponent".to_string(),
+                rounds.iter().map(|round| round.opponent).collect(),
+            )?),
+            StrategySpec::Random => Box::new(Random),
+            StrategySpec::Frequency => Box::new(FrequencyCounter),
+            StrategySpec::BeatLast => Box::new(BeatLast),
+            StrategySpec::Always(m) => Box::new(Fixed::new(format!("always {m}"), vec![m])?),
+        })
+    }
+}
+
+impl FromStr for StrategySpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "guide" => Ok(StrategySpec::Guide),
+            "guide-opponent" => Ok(StrategySpec::GuideOpponent),
+            "random" => Ok(StrategySpec::Random),
+            "frequency" => Ok(StrategySpec::Frequency),
+            "beat-last" => Ok(StrategySpec::BeatLast),
+            other => other.parse().map(StrategySpec::Always).map_err(|_| {
+                format!(
+                    "unknown strategy {other:?}, expected guide, guide-opponent, random, \
+                     frequency, beat-last, rock, paper or scissors"
+                )
+            }),
+        }
+    }
+}
+
+/// How one match between two strategies went, from the first player's point of view.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MatchResult {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: u64,
+    pub opponent_score: u64,
+}
+
+/// Plays `rounds` rounds of `me` against `opponent`.
+pub fn play_match(
+    me: &dyn Strategy,
+    opponent: &dyn Strategy,
+    rounds: usize,
+    rng: &mut SimRng,
+) -> MatchResult {
+    // `rounds` comes straight from the command line, so the histories grow as they go rather
+    // than reserving it all up front.
+    let mut mine = Vec::new();
+    let mut theirs = Vec::new();
+    let (mut my_counts, mut their_counts) = ([0; 3], [0; 3]);
+    let mut result = MatchResult::default();
+
+    for _ in 0..rounds {
+        let my_move = me.next_move(
+            History {
+                mine: &mine,
+                theirs: &theirs,
+                their_counts,
+            },
+            rng,
+        );
+        let their_move = opponent.next_move(
+            History {
+                mine: &theirs,
+                theirs: &mine,
+                their_counts: my_counts,
+            },
+            rng,
+        );
+        match outcome(their_move, my_move) {
+            Outcome::Win => result.wins += 1,
+            Outcome::Draw => result.draws += 1,
+            Outcome::Lose => result.losses += 1,
+        }
+        result.score += u64::from(round_score(their_move, my_move));
+        result.opponent_score += u64::from(round_score(my_move, their_move));
+        mine.push(my_move);
+        theirs.push(their_move);
+        my_counts[my_move.shape().index()] += 1;
+        their_counts[their_move.shape().index()] += 1;
+    }
+    result
+}
+
+/// Many matches of the same length between two strategies, all drawn from one seed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Tournament {
+    pub matches: usize,
+    pub rounds: usize,
+    pub seed: u64,
+}
+
+impl Tournament {
+    pub fn run(&self, me: &dyn Strategy, opponent: &dyn Strategy) -> Report {
+        let mut rng = SimRng::seed_from_u64(self.seed);
+        let matches = (0..self.matches)
+            .map(|_| play_match(me, opponent, self.rounds, &mut rng))
+            .collect();
+        Report {
+            me: me.name(),
+            opponent: opponent.name(),
+            tournament: *self,
+            matches,
+        }
+    }
+}
+
+/// Summary of a sample of per-match scores.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Distribution {
+    pub min: u64,
+    pub p25: u64,
+    pub median: u64,
+    pub p75: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl Distribution {
+    /// Summarises `scores`, using nearest-rank percentiles; all zero if there are none.
+    pub fn of(scores: &[u64]) -> Self {
+        let mut sorted = scores.to_vec();
+        sorted.sort_unstable();
+        if sorted.is_empty() {
+            return Distribution::default();
+        }
+        let rank = |p: usize| sorted[(p * (sorted.len() - 1)).div_ceil(100)];
+        let mean = sorted.iter().map(|&s| s as f64).sum::<f64>() / sorted.len() as f64;
+        let variance = sorted
+            .iter()
+            .map(|&s| (s as f64 - mean).powi(2))
+            .sum::<f64>()
+            / sorted.len() as f64;
+        Distribution {
+            min: sorted[0],
+            p25: rank(25),
+            median: rank(50),
+            p75: rank(75),
+            max: sorted[sorted.len() - 1],
+            mean,
+            std_dev: variance.sqrt(),
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:.1} (sd {:.1}), min {}, p25 {}, median {}, p75 {}, max {}",
+            self.mean, self.std_dev, self.min, self.p25, self.median, self.p75, self.max
+        )
+    }
+}
+
+/// Every match of a [`Tournament`], with rates and score distributions over them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Report {
+    pub me: String,
+    pub opponent: String,
+    pub tournament: Tournament,
+    pub matches: Vec<MatchResult>,
+}
+
+impl Report {
+    fn rate(&self, count: impl Fn(&MatchResult) -> usize) -> f64 {
+        let total = self.tournament.matches * self.tournament.rounds;
+        if total == 0 {
+            return 0.0;
+        }
+        self.matches.iter().map(count).sum::<usize>() as f64 / total as f64
+    }
+
+    /// Fraction of all rounds I won.
+    pub fn win_rate(&self) -> f64 {
+        self.rate(|m| m.wins)
+    }
+
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(|m| m.draws)
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        self.rate(|m| m.losses)
+    }
+
+    /// Distribution of my total score per match.
+    pub fn scores(&self) -> Distribution {
+        Distribution::of(&self.matches.iter().map(|m| m.score).collect::<Vec<_>>())
+    }
+
+    /// Distribution of the opponent's total score per match.
+    pub fn opponent_scores(&self) -> Distribution {
+        Distribution::of(
+            &self
+                .matches
+                .iter()
+                .map(|m| m.opponent_score)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Tournament {
+            matches,
+            rounds,
+            seed,
+        } = self.tournament;
+        writeln!(
+            f,
+            "{} vs {}: {matches} matches of {rounds} rounds, seed {seed}",
+            self.me, self.opponent
+        )?;
+        writeln!(
+            f,
+            "wins {:.1}%, draws {:.1}%, losses {:.1}%",
+            self.win_rate() * 100.0,
+            self.draw_rate() * 100.0,
+            self.loss_rate() * 100.0
+        )?;
+        writeln!(f, "my score:       {}", self.scores())?;
+        writeln!(f, "opponent score: {}", self.opponent_scores())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tournaments_replay_from_their_seed() -> anyhow::Result<()> {
+        let tournament = Tournament {
+            matches: 20,
+            rounds: 50,
+            seed: 7,
+        };
+        let first = tournament.run(&FrequencyCounter, &Random);
+        assert_eq!(first, tournament.run(&FrequencyCounter, &Random));
+        let rates = first.win_rate() + first.draw_rate() + first.loss_rate();
+        assert!((rates - 1.0).abs() < 1e-9);
+
+        // Against a constant move, beating the last move wins every round after the first.
+        let rock = StrategySpec::Always(Move::Rock).build(&[], &Encoding::default(), Part::One)?;
+        let report = tournament.run(&BeatLast, rock.as_ref());
+        assert!(report.matches.iter().all(|m| m.wins >= 49));
+        assert!(report.opponent_scores().max <= 7 + 49);
+
+        // Following an empty guide has nothing to play.
+        assert!(matches!(
+            StrategySpec::Guide.build(&[], &Encoding::default(), Part::Two),
+            Err(StrategyError::NoMoves(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn distribution_uses_nearest_rank() {
+        let d = Distribution::of(&[5, 1, 4, 2, 3]);
+        assert_eq!((d.min, d.p25, d.median, d.p75, d.max), (1, 2, 3, 4, 5));
+        assert_eq!(d.mean, 3.0);
+    }
+
+    #[test]
+    fn strategies_parse_by_name_or_move() {
+        assert_eq!("beat-last".parse(), Ok(StrategySpec::BeatLast));
+        assert_eq!("paper".parse(), Ok(StrategySpec::Always(Move::Paper)));
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_core::Part;
+use serde::Serialize;
+
+use crate::{Encoding, Move, Outcome, ParseError, Round, outcome, round_score};
+
+/// One round as played under one part's reading of the guide.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub struct TracedRound {
+    pub part: u8,
+    /// 1-based line of the guide the round was read from.
+    pub line: usize,
+    pub opponent: Move,
+    /// The move from the guide in Part 1, or the one `required_move` picks in Part 2.
+    pub me: Move,
+    pub outcome: Outcome,
+    pub score: u32,
+    /// Sum of this round's score and every earlier one's in the same part.
+    pub total: u32,
+}
+
+/// Plays every round under `part`'s reading of `encoding`, in order.
+pub fn trace(
+    rounds: &[Round],
+    encoding: &Encoding,
+    part: Part,
+) -> Result<Vec<TracedRound>, ParseError> {
+    let mut total = 0;
+    rounds
+        .iter()
+        .map(|round| {
+            let me = round.my_move(part, encoding)?;
+            let score = round_score(round.opponent, me);
+            total += score;
+            Ok(TracedRound {
+                part: part.number(),
+                line: round.line,
+                opponent: round.opponent,
+                me,
+                outcome: outcome(round.opponent, me),
+                score,
+                total,
+            })
+        })
+        .collect()
+}
+
+/// How `--trace` renders rounds.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TraceFormat {
+    /// An aligned table per part.
+    #[default]
+    Text,
+    /// One header row, then a row per round of both parts.
+    Csv,
+    /// An array of round objects.
+    Json,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(TraceFormat::Text),
+            "csv" => Ok(TraceFormat::Csv),
+            "json" => Ok(TraceFormat::Json),
+            other => Err(format!(
+                "unknown trace format {other:?}, expected text, csv or json"
+            )),
+        }
+    }
+}
+
+/// Renders `rounds` as CSV, header first; no field ever needs quoting.
+pub fn to_csv(rounds: &[TracedRound]) -> String {
+    let mut csv = String::from("part,line,opponent,me,outcome,score,total\n");
+    for r in rounds {
+        csv += &format!(
+            "{},{},{},{},{},{},{}\n",
+            r.part, r.line, r.opponent, r.me, r.outcome, r.score, r.total
+        );
+    }
+    csv
+}
+
+/// Rounds of a single part, rendered as an aligned table.
+pub struct TraceTable<'a>(pub &'a [TracedRound]);
+
+impl fmt::Display for TraceTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>5}  {:<9} {:<9} {:<8} {:>5} {:>8}",
+            "line", "opponent", "me", "outcome", "score", "total"
+        )?;
+        for r in self.0 {
+            writeln!(
+                f,
+                "{:>5}  {:<9} {:<9} {:<8} {:>5} {:>8}",
+                r.line, r.opponent, r.me, r.outcome, r.score, r.total
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day02;
+    use aoc_core::Solution;
+
+    #[test]
+    fn trace_follows_each_round_to_the_answer() -> anyhow::Result<()> {
+        let guide = Day02::parse("A Y\n\nB X\nC Z\n")?;
+        let encoding = Encoding::default();
+
+        let two = trace(&guide, &encoding, Part::Two)?;
+        assert_eq!(
+            two[1],
+            TracedRound {
+                part: 2,
+                line: 3,
+                opponent: Move::Paper,
+                me: Move::Rock,
+                outcome: Outcome::Lose,
+                score: 1,
+                total: 5,
+            }
+        );
+        assert_eq!(two.last().unwrap().total, Day02::part_two(&guide)?);
+
+        let one = trace(&guide, &encoding, Part::One)?;
+        assert_eq!(one.last().unwrap().total, Day02::part_one(&guide)?);
+        assert_eq!(
+            to_csv(&one[..1]),
+            "part,line,opponent,me,outcome,score,total\n1,1,rock,paper,win,8,8\n"
+        );
+        Ok(())
+    }
+}